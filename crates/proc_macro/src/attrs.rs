@@ -0,0 +1,148 @@
+//! `#[df(...)]` attribute parsing, shared by the DataFrame derives
+
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Field, LitStr};
+
+/// Case conversion for `#[df(rename_all = "...")]`
+/// (same rule names as `#[sqlx(rename_all)]` / serde)
+#[derive(Debug, Clone, Copy)]
+pub enum RenameRule {
+        Lower,
+        Upper,
+        Snake,
+        ScreamingSnake,
+        Kebab,
+        Camel,
+        Pascal,
+}
+impl RenameRule {
+        pub fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+                Ok(match lit.value().as_str() {
+                        "lowercase" => Self::Lower,
+                        "UPPERCASE" => Self::Upper,
+                        "snake_case" => Self::Snake,
+                        "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+                        "kebab-case" => Self::Kebab,
+                        "camelCase" => Self::Camel,
+                        "PascalCase" => Self::Pascal,
+                        _ => {
+                                return Err(syn::Error::new_spanned(
+                                        lit,
+                                        "unknown rename_all rule, expected one of: lowercase, UPPERCASE, snake_case, \
+                                         SCREAMING_SNAKE_CASE, kebab-case, camelCase, PascalCase",
+                                ));
+                        }
+                })
+        }
+
+        pub fn apply(self, name: &str) -> String {
+                match self {
+                        Self::Lower => name.to_lowercase(),
+                        Self::Upper => name.to_uppercase(),
+                        Self::Snake => name.to_snake_case(),
+                        Self::ScreamingSnake => name.to_shouty_snake_case(),
+                        Self::Kebab => name.to_kebab_case(),
+                        Self::Camel => name.to_lower_camel_case(),
+                        Self::Pascal => name.to_upper_camel_case(),
+                }
+        }
+}
+
+/// Struct level `#[df(...)]` options
+#[derive(Debug, Default)]
+pub struct ContainerOpts {
+        pub rename_all: Option<RenameRule>,
+}
+impl ContainerOpts {
+        pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+                let mut opts = Self::default();
+                for attr in attrs.iter().filter(|a| a.path().is_ident("df")) {
+                        attr.parse_nested_meta(|meta| {
+                                if meta.path.is_ident("rename_all") {
+                                        opts.rename_all = Some(RenameRule::from_lit(&meta.value()?.parse()?)?);
+                                        Ok(())
+                                } else {
+                                        Err(meta.error("unsupported container attribute, expected `rename_all`"))
+                                }
+                        })?;
+                }
+                Ok(opts)
+        }
+}
+
+/// Field level `#[df(...)]` options
+#[derive(Debug, Default)]
+pub struct FieldOpts {
+        pub rename:   Option<String>,
+        pub skip:     bool,
+        pub dtype:    Option<TokenStream>,
+        /// the field is a `#[derive(SqlEnum)]` type (or an `Option` of one)
+        pub sql_enum: bool,
+}
+impl FieldOpts {
+        pub fn from_field(field: &Field) -> syn::Result<Self> {
+                let mut opts = Self::default();
+                for attr in field.attrs.iter().filter(|a| a.path().is_ident("df")) {
+                        attr.parse_nested_meta(|meta| {
+                                if meta.path.is_ident("rename") {
+                                        opts.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                                        Ok(())
+                                } else if meta.path.is_ident("skip") {
+                                        opts.skip = true;
+                                        Ok(())
+                                } else if meta.path.is_ident("dtype") {
+                                        opts.dtype = Some(dtype_tokens(&meta.value()?.parse()?)?);
+                                        Ok(())
+                                } else if meta.path.is_ident("sql_enum") {
+                                        opts.sql_enum = true;
+                                        Ok(())
+                                } else {
+                                        Err(meta.error(
+                                                "unsupported field attribute, expected `rename`, `skip`, `dtype` or \
+                                                 `sql_enum`",
+                                        ))
+                                }
+                        })?;
+                }
+                Ok(opts)
+        }
+
+        /// Column name: explicit rename > container rule > field ident
+        pub fn column_name(&self, field: &Field, container: &ContainerOpts) -> String {
+                if let Some(rename) = &self.rename {
+                        return rename.clone();
+                }
+                let ident = field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default();
+                let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+                match container.rename_all {
+                        Some(rule) => rule.apply(ident),
+                        None => ident.to_string(),
+                }
+        }
+}
+
+/// `#[df(dtype = "...")]` ~~> `polars::prelude::DataType` expression
+fn dtype_tokens(lit: &LitStr) -> syn::Result<TokenStream> {
+        let dt = quote!(::polars::prelude::DataType);
+        Ok(match lit.value().as_str() {
+                "categorical" => quote!(#dt::Categorical(None, ::polars::prelude::CategoricalOrdering::Physical)),
+                "string" | "str" => quote!(#dt::String),
+                "bool" => quote!(#dt::Boolean),
+                "i32" => quote!(#dt::Int32),
+                "i64" => quote!(#dt::Int64),
+                "u32" => quote!(#dt::UInt32),
+                "u64" => quote!(#dt::UInt64),
+                "f32" => quote!(#dt::Float32),
+                "f64" => quote!(#dt::Float64),
+                "date" => quote!(#dt::Date),
+                _ => {
+                        return Err(syn::Error::new_spanned(
+                                lit,
+                                "unknown dtype, expected one of: categorical, string, bool, i32, i64, u32, u64, f32, \
+                                 f64, date",
+                        ));
+                }
+        })
+}
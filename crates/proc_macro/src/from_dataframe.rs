@@ -0,0 +1,200 @@
+//! `#[derive(FromDataFrame)]` expansion
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::{DeriveInput, GenericArgument, PathArguments, Type};
+
+use crate::{attrs::{ContainerOpts, FieldOpts},
+            named_fields, option_inner};
+
+/// Rust field types we know how to pull out of a polars column
+#[derive(Debug, Clone, Copy)]
+enum ValueKind {
+        I32,
+        I64,
+        U32,
+        U64,
+        F32,
+        F64,
+        Bool,
+        String,
+        NaiveDate,
+}
+impl ValueKind {
+        fn from_ident(ident: &str) -> Option<Self> {
+                Some(match ident {
+                        "i32" => Self::I32,
+                        "i64" => Self::I64,
+                        "u32" => Self::U32,
+                        "u64" => Self::U64,
+                        "f32" => Self::F32,
+                        "f64" => Self::F64,
+                        "bool" => Self::Bool,
+                        "String" => Self::String,
+                        "NaiveDate" => Self::NaiveDate,
+                        _ => return None,
+                })
+        }
+
+        /// `Vec<Option<T>>` from a (materialized) `Series` named `series`
+        fn collect_values(self, series: &TokenStream) -> TokenStream {
+                match self {
+                        Self::I32 => quote!(#series.i32()?.into_iter().collect()),
+                        Self::I64 => quote!(#series.i64()?.into_iter().collect()),
+                        Self::U32 => quote!(#series.u32()?.into_iter().collect()),
+                        Self::U64 => quote!(#series.u64()?.into_iter().collect()),
+                        Self::F32 => quote!(#series.f32()?.into_iter().collect()),
+                        Self::F64 => quote!(#series.f64()?.into_iter().collect()),
+                        Self::Bool => quote!(#series.bool()?.into_iter().collect()),
+                        Self::String => {
+                                quote!(#series.str()?.into_iter().map(|v| v.map(::std::string::ToString::to_string)).collect())
+                        }
+                        Self::NaiveDate => quote!(#series.date()?.as_date_iter().collect()),
+                }
+        }
+
+        /// dtype the column must have for this kind
+        fn dtype(self) -> TokenStream {
+                let dt = quote!(::polars::prelude::DataType);
+                match self {
+                        Self::I32 => quote!(#dt::Int32),
+                        Self::I64 => quote!(#dt::Int64),
+                        Self::U32 => quote!(#dt::UInt32),
+                        Self::U64 => quote!(#dt::UInt64),
+                        Self::F32 => quote!(#dt::Float32),
+                        Self::F64 => quote!(#dt::Float64),
+                        Self::Bool => quote!(#dt::Boolean),
+                        Self::String => quote!(#dt::String),
+                        Self::NaiveDate => quote!(#dt::Date),
+                }
+        }
+}
+
+/// `T` or `Option<T>` with a supported `T`; `bool` is whether it was an `Option`
+fn classify(ty: &Type) -> Option<(bool, ValueKind)> {
+        let Type::Path(path) = ty else { return None };
+        let last = path.path.segments.last()?;
+        if last.ident == "Option" {
+                let PathArguments::AngleBracketed(args) = &last.arguments else { return None };
+                let Some(GenericArgument::Type(inner)) = args.args.first() else { return None };
+                let (nested, kind) = classify(inner)?;
+                return (!nested).then_some((true, kind));
+        }
+        ValueKind::from_ident(&last.ident.to_string()).map(|kind| (false, kind))
+}
+
+/// Row `__row` of the extracted `values`; a null into a non-`Option` field is an error
+fn field_value(
+        is_option: bool,
+        values: &TokenStream,
+        struct_name: &str,
+        col_name: &str,
+        field_name: &str,
+) -> TokenStream {
+        if is_option {
+                quote!(#values[__row].take())
+        } else {
+                quote! {
+                    #values[__row].take().ok_or_else(|| {
+                        ::polars::prelude::PolarsError::SchemaMismatch(
+                            ::std::format!(
+                                "{}: column `{}` row {} is null but field `{}` is not an Option",
+                                #struct_name, #col_name, __row, #field_name,
+                            )
+                            .into(),
+                        )
+                    })?
+                }
+        }
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+        let name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        let container = ContainerOpts::from_attrs(&input.attrs)?;
+        let fields = named_fields(input, "FromDataFrame")?;
+        let struct_name = name.to_string();
+
+        let mut extractions = Vec::new();
+        let mut inits = Vec::new();
+        for field in fields {
+                let opts = FieldOpts::from_field(field)?;
+                let ident = &field.ident;
+                // generated locals are `__`-prefixed so they can't clash with field names
+                let local = format_ident!("__col_{}", ident.as_ref().expect("named field"));
+                if opts.skip {
+                        inits.push(quote!(#ident: ::std::default::Default::default()));
+                        continue;
+                }
+                let col_name = opts.column_name(field, &container);
+                let field_name = ident.as_ref().map(|i| i.to_string()).unwrap_or_default();
+                if opts.sql_enum {
+                        let (is_option, inner) = option_inner(&field.ty);
+                        extractions.push(quote! {
+                            let mut #local: ::std::vec::Vec<::std::option::Option<#inner>> =
+                                <#inner>::from_series(__df.column(#col_name)?.as_materialized_series())?;
+                        });
+                        let value = field_value(is_option, &quote!(#local), &struct_name, &col_name, &field_name);
+                        inits.push(quote!(#ident: #value));
+                        continue;
+                }
+                let Some((is_option, kind)) = classify(&field.ty) else {
+                        return Err(syn::Error::new_spanned(
+                                &field.ty,
+                                format!(
+                                        "FromDataFrame does not support `{}`; expected i32, i64, u32, u64, f32, f64, \
+                                         bool, String, NaiveDate or an Option of one of those (or mark it #[df(skip)], or \
+                                         #[df(sql_enum)] for a SqlEnum)",
+                                        field.ty.to_token_stream()
+                                ),
+                        ));
+                };
+                let dtype = kind.dtype();
+                let series = quote!(__series);
+                let collect = kind.collect_values(&series);
+                extractions.push(quote! {
+                    let mut #local: ::std::vec::Vec<::std::option::Option<_>> = {
+                        let __column = __df.column(#col_name)?;
+                        let __expected = #dtype;
+                        // categoricals hold their values as strings
+                        let __column = match __column.dtype() {
+                            ::polars::prelude::DataType::Categorical(..) | ::polars::prelude::DataType::Enum(..)
+                                if __expected == ::polars::prelude::DataType::String =>
+                            {
+                                __column.cast(&__expected)?
+                            }
+                            _ => __column.clone(),
+                        };
+                        if __column.dtype() != &__expected {
+                            return ::std::result::Result::Err(::polars::prelude::PolarsError::SchemaMismatch(
+                                ::std::format!(
+                                    "{}: column `{}` has dtype {} but field `{}` needs {}",
+                                    #struct_name, #col_name, __column.dtype(), #field_name, __expected,
+                                )
+                                .into(),
+                            ));
+                        }
+                        let #series = __column.as_materialized_series();
+                        #collect
+                    };
+                });
+                let value = field_value(is_option, &quote!(#local), &struct_name, &col_name, &field_name);
+                inits.push(quote!(#ident: #value));
+        }
+
+        Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Polars::DataFrame ~~> rows, one per frame row
+                ///
+                /// Errors name the struct, column (and row, for unexpected nulls) that didn't fit.
+                pub fn from_dataframe(
+                    __df: &::polars::prelude::DataFrame,
+                ) -> ::polars::prelude::PolarsResult<::std::vec::Vec<Self>> {
+                    #(#extractions)*
+                    (0..__df.height())
+                        .map(|__row| ::std::result::Result::Ok(Self { #(#inits,)* }))
+                        .collect()
+                }
+            }
+        })
+}
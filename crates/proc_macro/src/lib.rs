@@ -1,45 +1,161 @@
 extern crate proc_macro;
 
-use polars::prelude::*;
+mod attrs;
+mod from_dataframe;
+mod sql_enum;
+mod sql_queries;
+mod to_dataframe;
+
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Data, DeriveInput, Fields, parse_macro_input};
+use syn::{Data, DeriveInput, Field, Fields, GenericArgument, LitStr, PathArguments, Type, parse_macro_input,
+          punctuated::Punctuated, token::Comma};
 
-#[proc_macro_derive(ToDataFrame)]
+/// Derive `to_dataframe` & `into_dataframe` for a struct with named fields.
+///
+/// Generates
+/// - `fn to_dataframe(rows: &[Self]) -> PolarsResult<DataFrame>`, cloning the field values
+/// - `fn into_dataframe(rows: impl IntoIterator<Item = Self>) -> PolarsResult<DataFrame>`, moving them
+///   (so the struct can't implement `Drop`)
+///
+/// One column per field, named after the field.
+///
+/// Every field type must be something `polars::prelude::Series::new` accepts as a `Vec<_>`
+/// (e.g. `i32`, `f64`, `String`, `NaiveDate`, and `Option`s of those), and `Clone` for `to_dataframe`.
+/// The deriving crate needs `polars` as a dependency.
+///
+/// ## Attributes
+/// - container: `#[df(rename_all = "PascalCase")]`
+///   (lowercase, UPPERCASE, snake_case, SCREAMING_SNAKE_CASE, kebab-case, camelCase, PascalCase)
+/// - field: `#[df(rename = "StudentID")]` column name, wins over `rename_all`
+/// - field: `#[df(skip)]` no column for this field (field needn't be `Clone`)
+/// - field: `#[df(dtype = "categorical")]` cast the column after building it
+///   (categorical, string, bool, i32, i64, u32, u64, f32, f64, date); a value that doesn't fit is an error, not a null
+/// - field: `#[df(sql_enum)]` the field is a `#[derive(SqlEnum)]` type (or an `Option` of one):
+///   the column is a polars `Enum` of its values
+///
+/// Mirrors `#[sqlx(rename)]`: note `rename_all = "PascalCase"` makes `student_id` ~~> `StudentId`,
+/// so acronyms still want an explicit `rename`.
+#[proc_macro_derive(ToDataFrame, attributes(df))]
 pub fn to_dataframe(input: TokenStream) -> TokenStream {
         let input = parse_macro_input!(input as DeriveInput);
-        let name = &input.ident;
-        let fields = match input.data {
-                Data::Struct(data) => match data.fields {
-                        Fields::Named(fields) => fields.named,
-                        _ => panic!("Unsupported struct type"),
+        match to_dataframe::expand(&input) {
+                Ok(tokens) => tokens.into(),
+                Err(e) => e.to_compile_error().into(),
+        }
+}
+
+/// Derive `from_dataframe` for a struct with named fields: the reverse of `ToDataFrame`.
+///
+/// Generates `fn from_dataframe(df: &DataFrame) -> PolarsResult<Vec<Self>>`,
+/// reading each field from the column of the same name (same `#[df(...)]` naming attributes as `ToDataFrame`,
+/// so a derived pair round-trips).
+///
+/// Supported field types: `i32`, `i64`, `u32`, `u64`, `f32`, `f64`, `bool`, `String`, `NaiveDate`,
+/// and `Option`s of those. `#[df(skip)]` fields are filled with `Default::default()`.
+///
+/// Checks, reported as `PolarsError::SchemaMismatch` naming struct, column (& row):
+/// - the column dtype must match the field type exactly (categorical columns are read as strings)
+/// - a null is only accepted into an `Option<_>` field
+/// - `#[df(sql_enum)]` fields take an `Enum`, categorical or string column whose values all parse
+#[proc_macro_derive(FromDataFrame, attributes(df))]
+pub fn from_dataframe(input: TokenStream) -> TokenStream {
+        let input = parse_macro_input!(input as DeriveInput);
+        match from_dataframe::expand(&input) {
+                Ok(tokens) => tokens.into(),
+                Err(e) => e.to_compile_error().into(),
+        }
+}
+
+/// Derive database & DataFrame mappings for a fieldless enum mirroring a SQL `ENUM` column.
+///
+/// Generates
+/// - `VARIANTS` / `NAMES` consts (declaration order) and `const fn as_str(&self) -> &'static str`
+/// - `Display`, `FromStr` & `TryFrom<&str>` (strum style: `Err = strum::ParseError`), `AsRef<str>`
+/// - `sqlx::Type`, `Decode` & `Encode` for any backend that stores it as text (MySQL `ENUM`, SQLite `TEXT`):
+///   a value that isn't one of the variants fails at decode, naming it and the allowed values
+/// - `polars_dtype()` (a polars `Enum`, categories in declaration order), `to_series(name, values)` &
+///   `from_series(&series)`; see `#[df(sql_enum)]` on `ToDataFrame` / `FromDataFrame` fields
+///
+/// Declare the variants in the database's order so polars sorts them the same way.
+/// The deriving crate needs `polars`, `sqlx` and `strum` as dependencies.
+///
+/// ## Attributes
+/// - container: `#[sql_enum(rename_all = "lowercase")]` (same rules as `#[df(rename_all)]`)
+/// - variant: `#[sql_enum(rename = "In Progress")]` database value, wins over `rename_all`
+#[proc_macro_derive(SqlEnum, attributes(sql_enum))]
+pub fn sql_enum(input: TokenStream) -> TokenStream {
+        let input = parse_macro_input!(input as DeriveInput);
+        match sql_enum::expand(&input) {
+                Ok(tokens) => tokens.into(),
+                Err(e) => e.to_compile_error().into(),
+        }
+}
+
+/// One typed `async fn` per `.sql` file in a directory, each a `sqlx::query_file!` call.
+///
+/// `sqlx::query_file*!` need a literal path per call; this writes those calls for you,
+/// keeping sqlx's compile-time checking.
+/// The directory is looked up relative to the crate's `Cargo.toml`, then its parent directories
+/// (so `sql_queries!("data/sql_queries")` finds the workspace's `data/`).
+///
+/// Per file `name.sql` (header format as in `xp_sqlx::query_file`):
+/// ```text
+/// -- description: doc comment for the fn
+/// -- returns: StudentQA            (optional: use `query_file_as!` into this type)
+/// -- param: student_id INT         (one per `?`, in order; becomes a typed argument)
+/// ```
+/// generates
+/// `pub async fn name(pool: &MySqlPool, student_id: i32) -> Result<Vec<StudentQA>, sqlx::Error>`.
+/// Without `returns` the rows are sqlx's anonymous records (`Vec<impl Debug>`).
+/// Subfolders become `pub mod`s. Undeclared `?`s are a compile error.
+///
+/// Note: adding a file doesn't by itself trigger a rebuild (edits to existing files do, via `query_file!`).
+#[proc_macro]
+pub fn sql_queries(input: TokenStream) -> TokenStream {
+        let dir = parse_macro_input!(input as LitStr);
+        match sql_queries::expand(&dir) {
+                Ok(tokens) => tokens.into(),
+                Err(e) => e.to_compile_error().into(),
+        }
+}
+
+/// The named fields of a struct, or a spanned error naming the derive that can't handle the shape
+fn named_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<&'a Punctuated<Field, Comma>> {
+        match &input.data {
+                Data::Struct(data) => match &data.fields {
+                        Fields::Named(fields) => Ok(&fields.named),
+                        Fields::Unnamed(_) => Err(syn::Error::new_spanned(
+                                &data.fields,
+                                format!(
+                                        "{derive} needs named fields to name the columns; tuple structs are not supported"
+                                ),
+                        )),
+                        Fields::Unit => Err(syn::Error::new_spanned(
+                                &input.ident,
+                                format!("{derive} has no columns to work with on a unit struct"),
+                        )),
                 },
-                _ => panic!("Unsupported data type"),
-        };
-
-        let field_names = fields.iter().map(|f| &f.ident);
-        let vec_creations = fields.iter().map(|f| {
-                let ident = &f.ident;
-                quote! { let mut #ident = Vec::with_capacity(len); }
-        });
-        let pushes = fields.iter().map(|f| {
-                let ident = &f.ident;
-                quote! { #ident.push(e.#ident.clone()); }
-        });
-
-        let gen = quote! {
-            impl #name {
-                pub fn to_dataframe(input: Vec<#name>) -> polars::prelude::DataFrame {
-                    let len = input.len();
-                    #(#vec_creations)*
-                    for e in input {
-                        #(#pushes)*
-                    }
-                    df! {
-                        #(#field_names => #field_names,)*
-                    }
+                Data::Enum(data) => Err(syn::Error::new_spanned(
+                        data.enum_token,
+                        format!("{derive} can only be derived for structs"),
+                )),
+                Data::Union(data) => Err(syn::Error::new_spanned(
+                        data.union_token,
+                        format!("{derive} can only be derived for structs"),
+                )),
+        }
+}
+
+/// `Option<T>` ~~> `(true, T)`, anything else `(false, ty)`
+fn option_inner(ty: &Type) -> (bool, &Type) {
+        if let Type::Path(path) = ty {
+                if let Some(last) = path.path.segments.last().filter(|last| last.ident == "Option") {
+                        if let PathArguments::AngleBracketed(args) = &last.arguments {
+                                if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                                        return (true, inner);
+                                }
+                        }
                 }
-            }
-        };
-        gen.into()
+        }
+        (false, ty)
 }
@@ -0,0 +1,209 @@
+//! `#[derive(SqlEnum)]` expansion
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr};
+
+use crate::attrs::RenameRule;
+
+/// `#[sql_enum(rename_all = "...")]` on the enum
+fn container_rule(attrs: &[Attribute]) -> syn::Result<Option<RenameRule>> {
+        let mut rule = None;
+        for attr in attrs.iter().filter(|a| a.path().is_ident("sql_enum")) {
+                attr.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("rename_all") {
+                                rule = Some(RenameRule::from_lit(&meta.value()?.parse()?)?);
+                                Ok(())
+                        } else {
+                                Err(meta.error("unsupported container attribute, expected `rename_all`"))
+                        }
+                })?;
+        }
+        Ok(rule)
+}
+
+/// `#[sql_enum(rename = "...")]` on a variant
+fn variant_rename(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+        let mut rename = None;
+        for attr in attrs.iter().filter(|a| a.path().is_ident("sql_enum")) {
+                attr.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("rename") {
+                                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                                Ok(())
+                        } else {
+                                Err(meta.error("unsupported variant attribute, expected `rename`"))
+                        }
+                })?;
+        }
+        Ok(rename)
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+        let name = &input.ident;
+        let type_name = name.to_string();
+        if !input.generics.params.is_empty() {
+                return Err(syn::Error::new_spanned(&input.generics, "SqlEnum does not support generic enums"));
+        }
+        let Data::Enum(data) = &input.data else {
+                return Err(syn::Error::new_spanned(&input.ident, "SqlEnum can only be derived for enums"));
+        };
+        let rule = container_rule(&input.attrs)?;
+
+        let mut variants = Vec::new();
+        let mut names = Vec::new();
+        for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                        return Err(syn::Error::new_spanned(
+                                &variant.fields,
+                                "SqlEnum variants are database values and can't carry fields",
+                        ));
+                }
+                let ident = variant.ident.to_string();
+                let value = match (variant_rename(&variant.attrs)?, rule) {
+                        (Some(rename), _) => rename,
+                        (None, Some(rule)) => rule.apply(&ident),
+                        (None, None) => ident,
+                };
+                if names.contains(&value) {
+                        return Err(syn::Error::new_spanned(variant, format!("duplicate value `{value}`")));
+                }
+                variants.push(&variant.ident);
+                names.push(value);
+        }
+        if variants.is_empty() {
+                return Err(syn::Error::new_spanned(&input.ident, "SqlEnum needs at least one variant"));
+        }
+
+        let pl = quote!(::polars::prelude);
+        Ok(quote! {
+            impl #name {
+                /// Every variant, in declaration order (which is also the polars `Enum`'s category order)
+                pub const VARIANTS: &'static [Self] = &[#(Self::#variants,)*];
+                /// Database value of each variant, in the same order
+                pub const NAMES: &'static [&'static str] = &[#(#names,)*];
+
+                /// The database value
+                pub const fn as_str(&self) -> &'static str {
+                    match self {
+                        #(Self::#variants => #names,)*
+                    }
+                }
+
+                /// polars `Enum` with `NAMES` as its (ordered) categories
+                pub fn polars_dtype() -> #pl::DataType {
+                    #pl::create_enum_dtype(
+                        ::polars::export::arrow::array::Utf8ViewArray::from_slice_values(Self::NAMES),
+                    )
+                }
+
+                /// Values ~~> `Enum` Series (nulls for `None`)
+                pub fn to_series<I>(name: #pl::PlSmallStr, values: I) -> #pl::PolarsResult<#pl::Series>
+                where
+                    I: ::std::iter::IntoIterator<Item = ::std::option::Option<Self>>,
+                {
+                    let names: ::std::vec::Vec<::std::option::Option<&'static str>> =
+                        values.into_iter().map(|value| value.map(|value| value.as_str())).collect();
+                    <#pl::Series as #pl::NamedFrom<_, _>>::new(name, names).strict_cast(&Self::polars_dtype())
+                }
+
+                /// `Enum`, categorical or string Series ~~> values; a string outside `NAMES` is an error
+                pub fn from_series(
+                    series: &#pl::Series,
+                ) -> #pl::PolarsResult<::std::vec::Vec<::std::option::Option<Self>>> {
+                    let strings = series.cast(&#pl::DataType::String)?;
+                    strings
+                        .str()?
+                        .into_iter()
+                        .map(|value| {
+                            value
+                                .map(|value| {
+                                    value.parse::<Self>().map_err(|_| {
+                                        #pl::PolarsError::SchemaMismatch(
+                                            ::std::format!(
+                                                "`{}` in column `{}` is not a {} ({})",
+                                                value,
+                                                series.name(),
+                                                #type_name,
+                                                Self::NAMES.join(", "),
+                                            )
+                                            .into(),
+                                        )
+                                    })
+                                })
+                                .transpose()
+                        })
+                        .collect()
+                }
+            }
+
+            impl ::std::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.pad(self.as_str())
+                }
+            }
+
+            impl ::std::str::FromStr for #name {
+                type Err = ::strum::ParseError;
+
+                fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    match s {
+                        #(#names => ::std::result::Result::Ok(Self::#variants),)*
+                        _ => ::std::result::Result::Err(::strum::ParseError::VariantNotFound),
+                    }
+                }
+            }
+
+            impl ::std::convert::TryFrom<&str> for #name {
+                type Error = ::strum::ParseError;
+
+                fn try_from(s: &str) -> ::std::result::Result<Self, Self::Error> {
+                    s.parse()
+                }
+            }
+
+            impl ::std::convert::AsRef<str> for #name {
+                fn as_ref(&self) -> &str {
+                    self.as_str()
+                }
+            }
+
+            impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #name
+            where
+                str: ::sqlx::Type<DB>,
+            {
+                fn type_info() -> DB::TypeInfo {
+                    <str as ::sqlx::Type<DB>>::type_info()
+                }
+
+                fn compatible(ty: &DB::TypeInfo) -> bool {
+                    <str as ::sqlx::Type<DB>>::compatible(ty)
+                }
+            }
+
+            impl<'r, DB: ::sqlx::Database> ::sqlx::Decode<'r, DB> for #name
+            where
+                &'r str: ::sqlx::Decode<'r, DB>,
+            {
+                fn decode(
+                    value: <DB as ::sqlx::Database>::ValueRef<'r>,
+                ) -> ::std::result::Result<Self, ::sqlx::error::BoxDynError> {
+                    let text = <&'r str as ::sqlx::Decode<'r, DB>>::decode(value)?;
+                    text.parse().map_err(|_| {
+                        ::std::format!("`{}` is not a {} ({})", text, #type_name, Self::NAMES.join(", ")).into()
+                    })
+                }
+            }
+
+            impl<'q, DB: ::sqlx::Database> ::sqlx::Encode<'q, DB> for #name
+            where
+                &'q str: ::sqlx::Encode<'q, DB>,
+            {
+                fn encode_by_ref(
+                    &self,
+                    buf: &mut <DB as ::sqlx::Database>::ArgumentBuffer<'q>,
+                ) -> ::std::result::Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError> {
+                    <&'q str as ::sqlx::Encode<'q, DB>>::encode_by_ref(&self.as_str(), buf)
+                }
+            }
+        })
+}
@@ -0,0 +1,282 @@
+//! `sql_queries!("dir")` expansion
+
+use std::path::{Path, PathBuf};
+
+use heck::ToSnakeCase;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Ident, LitStr, Type};
+
+/// One `-- param:` header line
+struct Param {
+        name:     Ident,
+        sql_type: String,
+}
+
+/// The header keys this macro cares about (same format as `xp_sqlx::query_file`)
+struct Header {
+        name:        Option<String>,
+        description: Option<String>,
+        returns:     Option<String>,
+        params:      Vec<Param>,
+}
+
+pub fn expand(dir: &LitStr) -> syn::Result<TokenStream> {
+        let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+                syn::Error::new(dir.span(), "sql_queries! needs CARGO_MANIFEST_DIR (run it through cargo)")
+        })?);
+        let (root, relative) = locate(&manifest_dir, &dir.value()).ok_or_else(|| {
+                syn::Error::new(
+                        dir.span(),
+                        format!(
+                                "directory `{}` not found in {} or any parent directory",
+                                dir.value(),
+                                manifest_dir.display()
+                        ),
+                )
+        })?;
+        expand_dir(&root, &relative, dir.span())
+}
+
+/// `dir` under the manifest dir or its nearest ancestor (e.g. the workspace root)
+/// Returns the absolute path & the same path relative to the manifest dir (what `query_file!` wants).
+fn locate(manifest_dir: &Path, dir: &str) -> Option<(PathBuf, PathBuf)> {
+        let mut relative = PathBuf::new();
+        for ancestor in manifest_dir.ancestors() {
+                let candidate = ancestor.join(dir);
+                if candidate.is_dir() {
+                        return Some((candidate, relative.join(dir)));
+                }
+                relative.push("..");
+        }
+        None
+}
+
+/// One fn per `.sql` file, one `pub mod` per subfolder
+fn expand_dir(dir: &Path, relative: &Path, span: Span) -> syn::Result<TokenStream> {
+        let io_error = |e: std::io::Error| syn::Error::new(span, format!("reading {}: {e}", dir.display()));
+        let mut entries = std::fs::read_dir(dir)
+                .map_err(io_error)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(io_error)?;
+        entries.sort();
+
+        let mut items = Vec::new();
+        for path in entries {
+                let file_name = path
+                        .file_name()
+                        .map(|f| f.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                let stem = path
+                        .file_stem()
+                        .map(|f| f.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                if path.is_dir() {
+                        let module = ident(&file_name, span)?;
+                        let inner = expand_dir(&path, &relative.join(&file_name), span)?;
+                        items.push(quote! {
+                            pub mod #module {
+                                #[allow(unused_imports)]
+                                use super::*;
+                                #inner
+                            }
+                        });
+                } else if path.extension().is_some_and(|ext| ext == "sql") {
+                        let sql = std::fs::read_to_string(&path)
+                                .map_err(|e| syn::Error::new(span, format!("reading {}: {e}", path.display())))?;
+                        let query_path = relative.join(&file_name);
+                        items.push(query_fn(&stem, &sql, &path, &query_path, span)?);
+                }
+        }
+        Ok(quote!(#(#items)*))
+}
+
+fn query_fn(stem: &str, sql: &str, path: &Path, query_path: &Path, span: Span) -> syn::Result<TokenStream> {
+        let file_error = |message: String| syn::Error::new(span, format!("{}: {message}", path.display()));
+        let header = parse_header(sql, span).map_err(|e| file_error(e.to_string()))?;
+        let placeholders = count_placeholders(sql);
+        if header.params.len() != placeholders {
+                return Err(file_error(format!(
+                        "has {placeholders} `?` placeholder(s) but declares {} `-- param:` line(s); \
+                         every parameter needs a `-- param: <name> <TYPE>` header to get a typed argument",
+                        header.params.len()
+                )));
+        }
+
+        let fn_name = ident(header.name.as_deref().unwrap_or(stem), span)?;
+        let query_path = query_path.to_string_lossy().replace('\\', "/");
+        let doc = header.description.unwrap_or_else(|| format!("`{query_path}`"));
+        let names: Vec<_> = header.params.iter().map(|p| &p.name).collect();
+        let types = header
+                .params
+                .iter()
+                .map(|p| rust_type(&p.sql_type).map_err(file_error))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+        let (output, query) = match &header.returns {
+                Some(returns) => {
+                        let returns: Type = syn::parse_str(returns)
+                                .map_err(|e| file_error(format!("`-- returns: {returns}`: {e}")))?;
+                        (quote!(#returns), quote!(::sqlx::query_file_as!(#returns, #query_path #(, #names)*)))
+                }
+                None => (quote!(impl ::std::fmt::Debug), quote!(::sqlx::query_file!(#query_path #(, #names)*))),
+        };
+        Ok(quote! {
+            #[doc = #doc]
+            pub async fn #fn_name(
+                pool: &::sqlx::MySqlPool,
+                #(#names: #types,)*
+            ) -> ::std::result::Result<::std::vec::Vec<#output>, ::sqlx::Error> {
+                #query.fetch_all(pool).await
+            }
+        })
+}
+
+fn ident(name: &str, span: Span) -> syn::Result<Ident> {
+        let snake = name.to_snake_case();
+        syn::parse_str::<Ident>(&snake)
+                .map(|i| Ident::new(&i.to_string(), span))
+                .map_err(|_| syn::Error::new(span, format!("`{name}` can't be made into a Rust identifier")))
+}
+
+/// Leading `--` lines: `name`, `description`, `returns`, `param`
+fn parse_header(sql: &str, span: Span) -> syn::Result<Header> {
+        let mut header = Header { name: None, description: None, returns: None, params: Vec::new() };
+        for line in sql.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                let Some(comment) = line.strip_prefix("--") else { break };
+                let Some((key, value)) = comment.split_once(':') else { continue };
+                let value = value.trim().to_string();
+                match key.trim() {
+                        "name" => header.name = Some(value),
+                        "description" => header.description = Some(value),
+                        "returns" => header.returns = Some(value),
+                        "param" => {
+                                // `<name> <TYPE> [= default] ["description"]`: only name & type matter here
+                                let spec = value.split(['=', '"']).next().unwrap_or_default();
+                                let mut words = spec.split_whitespace();
+                                let name = words.next().ok_or_else(|| syn::Error::new(span, "empty `-- param:`"))?;
+                                let sql_type = words.collect::<Vec<_>>().join(" ");
+                                if sql_type.is_empty() {
+                                        return Err(syn::Error::new(span, format!("param `{name}` has no type")));
+                                }
+                                header.params.push(Param { name: ident(name, span)?, sql_type });
+                        }
+                        _ => {}
+                }
+        }
+        Ok(header)
+}
+
+/// `?`s outside string literals, quoted identifiers & comments
+fn count_placeholders(sql: &str) -> usize {
+        let mut count = 0;
+        let mut chars = sql.chars().peekable();
+        while let Some(c) = chars.next() {
+                match c {
+                        '?' => count += 1,
+                        '\'' | '"' | '`' => {
+                                while let Some(next) = chars.next() {
+                                        if next == '\\' {
+                                                chars.next();
+                                        } else if next == c {
+                                                break;
+                                        }
+                                }
+                        }
+                        '-' if chars.peek() == Some(&'-') => {
+                                chars.by_ref().find(|&n| n == '\n');
+                        }
+                        '#' => {
+                                chars.by_ref().find(|&n| n == '\n');
+                        }
+                        '/' if chars.peek() == Some(&'*') => {
+                                chars.next();
+                                let mut prev = ' ';
+                                for next in chars.by_ref() {
+                                        if prev == '*' && next == '/' {
+                                                break;
+                                        }
+                                        prev = next;
+                                }
+                        }
+                        _ => {}
+                }
+        }
+        count
+}
+
+/// MySQL column type ~~> argument type
+///
+/// One of three SQL type tables; keep them in step (this crate can't depend on `xp-sqlx`):
+/// - `xp_sqlx::params::ParamKind::from_type_name`: what a runtime `?` parameter is parsed & bound as
+/// - `xp_sqlx::stream_to_df::mysql_column_kind`: what a result column decodes to (also `schema::Column::polars_dtype`)
+fn rust_type(sql_type: &str) -> Result<TokenStream, String> {
+        let upper = sql_type.to_ascii_uppercase();
+        let base = upper.split(['(', ' ']).next().unwrap_or_default();
+        let unsigned = upper.contains("UNSIGNED");
+        let chrono = quote!(::sqlx::types::chrono);
+        Ok(match base {
+                "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" if unsigned => quote!(u32),
+                "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" | "YEAR" => quote!(i32),
+                "BIGINT" if unsigned => quote!(u64),
+                "BIGINT" => quote!(i64),
+                "FLOAT" => quote!(f32),
+                "DOUBLE" | "REAL" => quote!(f64),
+                "BOOLEAN" | "BOOL" => quote!(bool),
+                "DATE" => quote!(#chrono::NaiveDate),
+                "DATETIME" | "TIMESTAMP" => quote!(#chrono::NaiveDateTime),
+                "TIME" => quote!(#chrono::NaiveTime),
+                // DECIMAL as text: MySQL converts it exactly
+                "DECIMAL" | "NUMERIC" | "CHAR" | "VARCHAR" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT" | "LONGTEXT"
+                | "ENUM" | "SET" | "JSON" => quote!(&str),
+                "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => quote!(&[u8]),
+                _ => return Err(format!("no Rust argument type for SQL type `{sql_type}`")),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+        use super::*;
+
+        fn rust(sql_type: &str) -> String {
+                rust_type(sql_type).unwrap().to_string().replace(' ', "")
+        }
+
+        #[test]
+        fn every_sql_type_has_its_argument_type() {
+                let chrono = "::sqlx::types::chrono::";
+                let cases = [
+                        ("INT", "i32".to_string()),
+                        ("int(11)", "i32".to_string()),
+                        ("TINYINT", "i32".to_string()),
+                        ("YEAR", "i32".to_string()),
+                        ("INT UNSIGNED", "u32".to_string()),
+                        ("smallint(5) unsigned", "u32".to_string()),
+                        ("BIGINT", "i64".to_string()),
+                        ("BIGINT UNSIGNED", "u64".to_string()),
+                        ("FLOAT", "f32".to_string()),
+                        ("DOUBLE", "f64".to_string()),
+                        ("BOOLEAN", "bool".to_string()),
+                        ("DATE", format!("{chrono}NaiveDate")),
+                        ("DATETIME", format!("{chrono}NaiveDateTime")),
+                        ("TIMESTAMP", format!("{chrono}NaiveDateTime")),
+                        ("TIME", format!("{chrono}NaiveTime")),
+                        ("DECIMAL(10, 2)", "&str".to_string()),
+                        ("VARCHAR(100)", "&str".to_string()),
+                        ("ENUM('A','B')", "&str".to_string()),
+                        ("JSON", "&str".to_string()),
+                        ("VARBINARY(16)", "&[u8]".to_string()),
+                        ("BLOB", "&[u8]".to_string()),
+                ];
+                for (sql_type, expected) in cases {
+                        assert_eq!(rust(sql_type), expected, "{sql_type}");
+                }
+        }
+
+        #[test]
+        fn unknown_sql_type_is_an_error() {
+                let e = rust_type("GEOMETRY").unwrap_err();
+                assert_eq!(e, "no Rust argument type for SQL type `GEOMETRY`");
+        }
+}
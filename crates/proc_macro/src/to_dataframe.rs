@@ -0,0 +1,76 @@
+//! `#[derive(ToDataFrame)]` expansion
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+use crate::{attrs::{ContainerOpts, FieldOpts},
+            named_fields, option_inner};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+        let name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        let container = ContainerOpts::from_attrs(&input.attrs)?;
+        let fields = named_fields(input, "ToDataFrame")?;
+
+        let mut idents = Vec::new();
+        // generated locals are `__`-prefixed so they can't clash with field names
+        let mut locals = Vec::new();
+        let mut types = Vec::new();
+        let mut columns = Vec::new();
+        for field in fields {
+                let opts = FieldOpts::from_field(field)?;
+                if opts.skip {
+                        continue;
+                }
+                let ident = &field.ident;
+                let local = format_ident!("__col_{}", ident.as_ref().expect("named field"));
+                let col_name = opts.column_name(field, &container);
+                let series = match option_inner(&field.ty) {
+                        (true, inner) if opts.sql_enum => quote!(<#inner>::to_series(#col_name.into(), #local)?),
+                        (false, ty) if opts.sql_enum => {
+                                quote!(<#ty>::to_series(#col_name.into(), #local.into_iter().map(::std::option::Option::Some))?)
+                        }
+                        _ => quote! {
+                            <::polars::prelude::Series as ::polars::prelude::NamedFrom<_, _>>::new(#col_name.into(), #local)
+                        },
+                };
+                let series = match &opts.dtype {
+                        Some(dtype) => quote!(#series.strict_cast(&#dtype)?),
+                        None => series,
+                };
+                idents.push(ident);
+                locals.push(local);
+                types.push(&field.ty);
+                columns.push(quote!(::polars::prelude::Column::from(#series)));
+        }
+
+        Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Borrowed rows ~~> Polars::DataFrame, one column per (non-skipped) field; clones the field values
+                pub fn to_dataframe(
+                    __rows: &[Self],
+                ) -> ::polars::prelude::PolarsResult<::polars::prelude::DataFrame> {
+                    #(let mut #locals: ::std::vec::Vec<#types> = ::std::vec::Vec::with_capacity(__rows.len());)*
+                    for __row in __rows {
+                        #(#locals.push(::std::clone::Clone::clone(&__row.#idents));)*
+                    }
+                    ::polars::prelude::DataFrame::new(::std::vec![#(#columns,)*])
+                }
+
+                /// Owned rows ~~> Polars::DataFrame, moving the field values into the columns
+                pub fn into_dataframe<I>(__rows: I) -> ::polars::prelude::PolarsResult<::polars::prelude::DataFrame>
+                where
+                    I: ::std::iter::IntoIterator<Item = Self>,
+                {
+                    let __rows = __rows.into_iter();
+                    let __len = __rows.size_hint().0;
+                    #(let mut #locals: ::std::vec::Vec<#types> = ::std::vec::Vec::with_capacity(__len);)*
+                    for __row in __rows {
+                        #(#locals.push(__row.#idents);)*
+                    }
+                    ::polars::prelude::DataFrame::new(::std::vec![#(#columns,)*])
+                }
+            }
+        })
+}
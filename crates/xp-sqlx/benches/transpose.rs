@@ -0,0 +1,45 @@
+//! Transposes alone: synthetic `students` rows ~~> polars::DataFrame, no database
+//!
+//! `cargo bench --bench transpose`; `-- <filter>` to pick, e.g. `-- builders`.
+//! Inputs are generated outside the timed section; allocations are counted by divan's `AllocProfiler`.
+
+use divan::{AllocProfiler, Bencher};
+use xp_sqlx::{bench::synthetic_students,
+              stream_to_df::{StudentQA, builders_transpose, series_transpose, struct_of_v_transpose,
+                             v_of_struct_transpose}};
+
+#[global_allocator]
+static ALLOC: AllocProfiler = AllocProfiler::system();
+
+/// rows per DataFrame
+const ROWS: [usize; 5] = [1, 1_000, 100_000, 1_000_000, 10_000_000];
+
+fn main() {
+        divan::main();
+}
+
+fn students(n: usize) -> Vec<StudentQA> {
+        synthetic_students(n).collect()
+}
+
+#[divan::bench(args = ROWS, sample_count = 10)]
+fn v_of_struct(bencher: Bencher, n: usize) {
+        bencher.with_inputs(|| students(n)).bench_values(v_of_struct_transpose);
+}
+
+#[divan::bench(args = ROWS, sample_count = 10)]
+fn struct_of_v(bencher: Bencher, n: usize) {
+        bencher.with_inputs(|| students(n)).bench_values(struct_of_v_transpose);
+}
+
+#[divan::bench(args = ROWS, sample_count = 10)]
+fn series(bencher: Bencher, n: usize) {
+        bencher.with_inputs(|| students(n))
+                .bench_refs(|students| series_transpose(students));
+}
+
+#[divan::bench(args = ROWS, sample_count = 10)]
+fn builders(bencher: Bencher, n: usize) {
+        bencher.with_inputs(|| students(n))
+                .bench_refs(|students| builders_transpose(students.iter()));
+}
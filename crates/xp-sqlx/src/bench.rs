@@ -0,0 +1,290 @@
+//! In-process benchmarking of the `stream_to_df` transpose implementations
+//!
+//! divan can't drive async code, so this is a small runner of our own:
+//! each implementation reports its time & allocations split into *fetch* (waiting on the row stream)
+//! and *transpose* (everything else), and `summarize` turns many runs into mean/σ/min/max.
+//!
+//! Allocation counts only move when a bin installs `CountingAllocator` as its `#[global_allocator]`.
+//!
+//! `synthetic_students` stands in for the database, so the transposes alone can be benched (see `benches/`).
+
+use std::{alloc::{GlobalAlloc, Layout, System},
+          sync::atomic::{AtomicU64, Ordering},
+          time::{Duration, Instant}};
+
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use derive_more::Display;
+use futures::{Stream, TryStreamExt};
+use polars::prelude::DataFrame;
+use sqlx::{ColumnIndex, Database, Decode, Executor, FromRow, IntoArguments, Pool, Type};
+
+use crate::{connection::DbPool,
+            enums::School,
+            error::Result,
+            stream_to_df::{StudentQA, builders_to_dataframe, series_to_dataframe, struct_of_v_macro,
+                           v_of_struct_macro}};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// `System`, counting every allocation (and reallocation) & the bytes asked for
+pub struct CountingAllocator;
+unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+                ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+                unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+                ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+                ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+                unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+                ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+                ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+                unsafe { System.realloc(ptr, layout, new_size) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                unsafe { System.dealloc(ptr, layout) }
+        }
+}
+
+/// Running totals of `CountingAllocator`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocCount {
+        pub allocations: u64,
+        pub bytes:       u64,
+}
+impl AllocCount {
+        pub fn now() -> Self {
+                Self {
+                        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+                        bytes:       ALLOCATED_BYTES.load(Ordering::Relaxed),
+                }
+        }
+
+        fn since(self, start: Self) -> Self {
+                Self { allocations: self.allocations - start.allocations, bytes: self.bytes - start.bytes }
+        }
+}
+
+/// Cost of one phase of a run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cost {
+        pub time:   Duration,
+        pub allocs: AllocCount,
+}
+impl Cost {
+        /// Cost from a `start()` until now
+        pub fn since(start: (Instant, AllocCount)) -> Self {
+                Self { time: start.0.elapsed(), allocs: AllocCount::now().since(start.1) }
+        }
+
+        pub fn start() -> (Instant, AllocCount) {
+                (Instant::now(), AllocCount::now())
+        }
+
+        fn add(&mut self, other: Self) {
+                self.time += other.time;
+                self.allocs.allocations += other.allocs.allocations;
+                self.allocs.bytes += other.allocs.bytes;
+        }
+
+        /// `self` minus a part of it
+        fn without(self, part: Self) -> Self {
+                Self { time: self.time.saturating_sub(part.time), allocs: self.allocs.since(part.allocs) }
+        }
+}
+
+/// One run of a transpose implementation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunCost {
+        /// waiting on the database row stream
+        pub fetch:     Cost,
+        /// the rest: collecting, transposing & building the DataFrame
+        pub transpose: Cost,
+        pub rows:      usize,
+}
+
+/// Splits a run into fetch & transpose: wrap the row stream's `try_next`s in `next`, then `finish`
+pub(crate) struct RunTimer {
+        start: (Instant, AllocCount),
+        fetch: Cost,
+        rows:  usize,
+}
+impl RunTimer {
+        pub(crate) fn start() -> Self {
+                Self { start: Cost::start(), fetch: Cost::default(), rows: 0 }
+        }
+
+        /// Next row, its wait charged to fetch
+        pub(crate) async fn next<S, T>(&mut self, stream: &mut S) -> Result<Option<T>>
+        where
+                S: Stream<Item = Result<T, sqlx::Error>> + Unpin,
+        {
+                let start = Cost::start();
+                let row = stream.try_next().await?;
+                self.fetch.add(Cost::since(start));
+                self.rows += usize::from(row.is_some());
+                Ok(row)
+        }
+
+        pub(crate) fn finish(self) -> RunCost {
+                let total = Cost::since(self.start);
+                RunCost { fetch: self.fetch, transpose: total.without(self.fetch), rows: self.rows }
+        }
+}
+
+/// The ways of getting rows into a DataFrame that we compare
+#[derive(ValueEnum, Display, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransImpl {
+        #[display("struct-of-v")]
+        StructOfV,
+        // set to 'v-struct', but not worth hunting for more syntax to get rename = "lower" to work
+        #[display("v-of-struct")]
+        VOfStruct,
+        #[display("series-to-df")]
+        SeriesToDF,
+        /// rows appended straight into arrow builders (no intermediate Vec<field>s)
+        #[display("builders")]
+        Builders,
+}
+impl TransImpl {
+        /// Draws the `students` table `repeats` times into one DataFrame
+        pub async fn run<DB>(self, pool: &Pool<DB>, repeats: u32) -> Result<(DataFrame, RunCost)>
+        where
+                DB: Database,
+                for<'r> StudentQA: FromRow<'r, DB::Row>,
+                for<'r> i32: Decode<'r, DB> + Type<DB>,
+                for<'r> &'r str: Decode<'r, DB> + Type<DB>,
+                for<'r> NaiveDate: Decode<'r, DB> + Type<DB>,
+                for<'r> School: Decode<'r, DB> + Type<DB>,
+                usize: ColumnIndex<DB::Row>,
+                for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+                for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+        {
+                match self {
+                        Self::VOfStruct => v_of_struct_macro(pool, repeats).await,
+                        Self::StructOfV => struct_of_v_macro(pool, repeats).await,
+                        Self::SeriesToDF => series_to_dataframe(pool, repeats).await,
+                        Self::Builders => builders_to_dataframe(pool, repeats).await,
+                }
+        }
+
+        /// `run` on whichever backend `pool` is
+        pub async fn run_any(self, pool: &DbPool, repeats: u32) -> Result<(DataFrame, RunCost)> {
+                match pool {
+                        DbPool::MySql(pool) => self.run(pool, repeats).await,
+                        DbPool::Postgres(pool) => self.run(pool, repeats).await,
+                        DbPool::Sqlite(pool) => self.run(pool, repeats).await,
+                }
+        }
+}
+
+/// mean/σ/min/max of some samples
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Summary {
+        pub mean:    f64,
+        /// sample standard deviation (0 for a single sample)
+        pub std_dev: f64,
+        pub min:     f64,
+        pub max:     f64,
+}
+impl Summary {
+        pub fn of(samples: &[f64]) -> Self {
+                if samples.is_empty() {
+                        return Self::default();
+                }
+                let n = samples.len() as f64;
+                let mean = samples.iter().sum::<f64>() / n;
+                let variance = match samples.len() {
+                        1 => 0.0,
+                        _ => samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0),
+                };
+                Self {
+                        mean,
+                        std_dev: variance.sqrt(),
+                        min: samples.iter().copied().fold(f64::INFINITY, f64::min),
+                        max: samples.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                }
+        }
+}
+
+/// Milliseconds & allocation counts of one phase over many runs
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PhaseSummary {
+        pub millis:      Summary,
+        pub allocations: Summary,
+        pub bytes:       Summary,
+}
+impl PhaseSummary {
+        pub fn of(costs: impl Iterator<Item = Cost> + Clone) -> Self {
+                let column = |f: fn(&Cost) -> f64| Summary::of(&costs.clone().map(|c| f(&c)).collect::<Vec<_>>());
+                Self {
+                        millis:      column(|c| c.time.as_secs_f64() * 1e3),
+                        allocations: column(|c| c.allocs.allocations as f64),
+                        bytes:       column(|c| c.allocs.bytes as f64),
+                }
+        }
+}
+
+/// Fetch, transpose & total over many runs
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunSummary {
+        pub fetch:     PhaseSummary,
+        pub transpose: PhaseSummary,
+        pub total:     PhaseSummary,
+}
+pub fn summarize(runs: &[RunCost]) -> RunSummary {
+        let total = runs.iter().map(|r| {
+                let mut total = r.fetch;
+                total.add(r.transpose);
+                total
+        });
+        RunSummary {
+                fetch:     PhaseSummary::of(runs.iter().map(|r| r.fetch)),
+                transpose: PhaseSummary::of(runs.iter().map(|r| r.transpose)),
+                total:     PhaseSummary::of(total),
+        }
+}
+
+const FIRST_NAMES: [&str; 8] = ["John", "Jane", "Alice", "Bob", "Carol", "Dmitri", "Eun-ji", "Oluwaseun"];
+const LAST_NAMES: [&str; 8] = ["Doe", "Smith", "Johnson", "Brown", "Nakamura", "Okafor", "García", "Lindqvist"];
+
+/// `n` made-up `students` rows, shaped like the seed data (a few NULL dob/school/email)
+///
+/// Deterministic: row `i` is always the same, so runs (and commits) compare like for like.
+pub fn synthetic_students(n: usize) -> impl Iterator<Item = StudentQA> {
+        let epoch = NaiveDate::from_ymd_opt(1990, 1, 1).expect("valid date");
+        (0..n).map(move |i| {
+                let bits = splitmix64(i as u64);
+                let pick = |shift: u32, len: usize| ((bits >> shift) as usize) % len;
+                let first_name = FIRST_NAMES[pick(0, FIRST_NAMES.len())];
+                let last_name = LAST_NAMES[pick(8, LAST_NAMES.len())];
+                // ~1 in 16 NULL each
+                let present = |shift: u32| (bits >> shift) & 0xF != 0;
+                StudentQA {
+                        id:         i32::try_from(i + 1).unwrap_or(i32::MAX),
+                        first_name: first_name.to_string(),
+                        last_name:  last_name.to_string(),
+                        dob:        present(16).then(|| epoch + chrono::Days::new((bits >> 24) % (20 * 365))),
+                        school:     present(20).then(|| School::VARIANTS[pick(40, School::VARIANTS.len())]),
+                        email:      present(48).then(|| {
+                                format!("{}.{}{i}@example.com", first_name.to_lowercase(), last_name.to_lowercase())
+                        }),
+                }
+        })
+}
+
+/// splitmix64: a well-mixed 64 bits per index, no rng state or dependency needed
+fn splitmix64(x: u64) -> u64 {
+        let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+}
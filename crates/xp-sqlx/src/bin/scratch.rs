@@ -1,4 +1,6 @@
+use clap::Parser;
 use polars::prelude::*;
+use xp_sqlx::{error::Error, telemetry::TelemetryArgs};
 
 #[derive(Debug)]
 struct Record {
@@ -7,20 +9,30 @@ struct Record {
         active: bool,
 }
 
-fn main() {
+/// polars scratchpad
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+        #[command(flatten)]
+        telemetry: TelemetryArgs,
+}
+
+fn main() -> Result<(), Error> {
+        let _telemetry = Args::parse().telemetry.init("scratch")?;
         // Create instances of the struct
-        let records = vec![Record { id: 1, name: "Alice".into(), active: true }, Record {
+        let records = [Record { id: 1, name: "Alice".into(), active: true }, Record {
                 id:     2,
                 name:   "Bob".into(),
                 active: false,
         }];
 
         // Convert struct instances to Series
-        let id_series = Series::new("a", records.iter().map(|r| r.id).collect::<Vec<_>>());
-        let name_series = Series::new("b", records.iter().map(|r| &r.name).cloned().collect::<Vec<_>>());
-        let active_series = Series::new("c", records.iter().map(|r| r.active).collect::<Vec<_>>());
+        let id_series = Series::new("a".into(), records.iter().map(|r| r.id).collect::<Vec<_>>());
+        let name_series = Series::new("b".into(), records.iter().map(|r| &r.name).cloned().collect::<Vec<_>>());
+        let active_series = Series::new("c".into(), records.iter().map(|r| r.active).collect::<Vec<_>>());
 
         // Create a DataFrame
-        let df = DataFrame::new(vec![id_series, name_series, active_series]).unwrap();
+        let df = DataFrame::new(vec![id_series.into(), name_series.into(), active_series.into()]).unwrap();
         println!("{:?}", df);
+        Ok(())
 }
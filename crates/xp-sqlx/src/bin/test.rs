@@ -1,15 +1,89 @@
+use chrono::NaiveDate;
+use clap::Parser;
 use polars::prelude::*;
-use proc_macro::ToDataFrame;
+use proc_macro::{FromDataFrame, ToDataFrame};
+use xp_sqlx::{enums::School, error::Error, telemetry::TelemetryArgs};
 
-// #[derive(ToDataFrame)]
+#[derive(Debug, ToDataFrame, FromDataFrame)]
 struct MyStruct {
         field1: i32,
         field2: String,
         field3: f64,
 }
 
-fn main() {
+/// `StudentQA`-shaped: Options, dates, strings and an ENUM
+/// with column names matching the `students` table
+#[derive(Debug, ToDataFrame, FromDataFrame)]
+#[df(rename_all = "PascalCase")]
+struct StudentQA {
+        #[df(rename = "StudentID")]
+        id:         i32,
+        first_name: String,
+        last_name:  String,
+        #[df(rename = "DateOfBirth")]
+        dob:        Option<NaiveDate>,
+        #[df(sql_enum)]
+        school:     Option<School>,
+        #[df(skip)]
+        email:      Option<String>,
+}
+
+/// `ToDataFrame` / `FromDataFrame` round trips
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+        #[command(flatten)]
+        telemetry: TelemetryArgs,
+}
+
+fn main() -> Result<(), Error> {
+        let _telemetry = Args::parse().telemetry.init("test")?;
         // Now you can directly use
         let my_data = vec![MyStruct { field1: 1, field2: "test".to_string(), field3: 3.12534 }];
-        // let df = MyStruct::to_dataframe(my_data);
+        let df = MyStruct::into_dataframe(my_data).expect("columns of equal length");
+        println!("{:?}", df);
+        // and back again
+        let round_trip = MyStruct::from_dataframe(&df).expect("frame made from MyStruct");
+        println!("{:?}", round_trip);
+
+        let students = vec![
+                StudentQA {
+                        id:         1,
+                        first_name: "John".to_string(),
+                        last_name:  "Doe".to_string(),
+                        dob:        NaiveDate::from_ymd_opt(1998, 4, 23),
+                        school:     Some(School::Sciences),
+                        email:      Some("john.doe@example.com".to_string()),
+                },
+                StudentQA {
+                        id:         2,
+                        first_name: "Jane".to_string(),
+                        last_name:  "Smith".to_string(),
+                        dob:        None,
+                        school:     None,
+                        email:      None,
+                },
+        ];
+        // borrowed: `students` is still usable afterwards
+        let df = StudentQA::to_dataframe(&students).expect("columns of equal length");
+        println!("{:?}", df);
+        // skipped fields come back as `Default::default()`
+        let round_trip = StudentQA::from_dataframe(&df).expect("frame made from StudentQA");
+        println!("{:?}", round_trip);
+
+        // a frame that doesn't fit is reported, naming column and row
+        let misfit = df
+                .clone()
+                .lazy()
+                .with_column(lit(NULL).cast(DataType::String).alias("LastName"))
+                .collect();
+        match misfit.and_then(|misfit| StudentQA::from_dataframe(&misfit)) {
+                Ok(students) => println!("unexpectedly fit: {:?}", students),
+                Err(e) => println!("{}", e),
+        }
+
+        // skipped in the frame, still on the struct
+        let emails: Vec<_> = students.iter().map(|s| &s.email).collect();
+        println!("{:?}", emails);
+        Ok(())
 }
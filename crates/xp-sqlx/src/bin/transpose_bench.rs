@@ -0,0 +1,159 @@
+//! In-process benchmark of the transpose implementations
+//!
+//! For every implementation × repetition count: some warmup runs, then `--iterations` timed runs,
+//! each split into DB-fetch & transpose time (plus allocations, via a counting global allocator).
+//! Results are one row per (implementation, repetitions, phase); `--output` them as `.csv`/`.json`
+//! and tag with the commit to compare across commits.
+
+use std::process::Command;
+
+use clap::{Parser, ValueEnum};
+use polars::prelude::*;
+use xp_sqlx::{bench::{CountingAllocator, PhaseSummary, RunCost, TransImpl, summarize},
+              connection::ConnectionArgs,
+              error::Error,
+              export::OutputArgs,
+              telemetry::TelemetryArgs};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+/// Times (and counts allocations of) the sqlx stream ~~> polars::DataFrame implementations
+///
+/// Note: This requires an active DataBase (see `transpose_implementations`; `sqlite::memory: --seed` works too).
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+        /// implementations to compare (all when omitted)
+        #[arg(short, long = "impl", value_delimiter = ',')]
+        implementations: Vec<TransImpl>,
+        /// numbers of times the table is drawn per run
+        #[arg(short, long, value_delimiter = ',', default_value = "1,10")]
+        repetitions:     Vec<u32>,
+        /// untimed runs before measuring
+        #[arg(short, long, default_value_t = 2)]
+        warmup:          u32,
+        /// timed runs
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations:      u32,
+        #[command(flatten)]
+        output:          OutputArgs,
+        #[command(flatten)]
+        connection:      ConnectionArgs,
+        #[command(flatten)]
+        telemetry:       TelemetryArgs,
+}
+
+/// Columns of the results table, one entry per row
+#[derive(Default)]
+struct Results {
+        implementation: Vec<String>,
+        repetitions:    Vec<u32>,
+        rows:           Vec<u64>,
+        phase:          Vec<&'static str>,
+        mean_ms:        Vec<f64>,
+        std_ms:         Vec<f64>,
+        min_ms:         Vec<f64>,
+        max_ms:         Vec<f64>,
+        allocations:    Vec<f64>,
+        alloc_bytes:    Vec<f64>,
+}
+impl Results {
+        fn push(
+                &mut self,
+                implementation: TransImpl,
+                repetitions: u32,
+                rows: usize,
+                phase: &'static str,
+                summary: PhaseSummary,
+        ) {
+                self.implementation.push(implementation.to_string());
+                self.repetitions.push(repetitions);
+                self.rows.push(rows as u64);
+                self.phase.push(phase);
+                self.mean_ms.push(summary.millis.mean);
+                self.std_ms.push(summary.millis.std_dev);
+                self.min_ms.push(summary.millis.min);
+                self.max_ms.push(summary.millis.max);
+                self.allocations.push(summary.allocations.mean);
+                self.alloc_bytes.push(summary.bytes.mean);
+        }
+
+        fn into_dataframe(self, commit: &str, iterations: u32) -> PolarsResult<DataFrame> {
+                let len = self.phase.len();
+                df! {
+                        "commit" => vec![commit; len],
+                        "impl" => self.implementation,
+                        "repetitions" => self.repetitions,
+                        "rows" => self.rows,
+                        "iterations" => vec![iterations; len],
+                        "phase" => self.phase,
+                        "mean_ms" => self.mean_ms,
+                        "std_ms" => self.std_ms,
+                        "min_ms" => self.min_ms,
+                        "max_ms" => self.max_ms,
+                        "allocations" => self.allocations,
+                        "alloc_bytes" => self.alloc_bytes,
+                }
+        }
+}
+
+/// short hash of `HEAD`, "unknown" outside a git checkout
+fn current_commit() -> String {
+        Command::new("git")
+                .args(["rev-parse", "--short", "HEAD"])
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .and_then(|out| String::from_utf8(out.stdout).ok())
+                .map(|hash| hash.trim().to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Error> {
+        let args = Args::parse();
+        let _telemetry = args.telemetry.init("transpose_bench")?;
+        if args.iterations == 0 {
+                return Err(Error::Input("--iterations must be at least 1".into()));
+        }
+        let implementations = if args.implementations.is_empty() {
+                TransImpl::value_variants().to_vec()
+        } else {
+                args.implementations.clone()
+        };
+        let pool = args.connection.connect_any().await.map_err(Error::Connection)?;
+
+        let mut results = Results::default();
+        for &implementation in &implementations {
+                for &reps in &args.repetitions {
+                        for _ in 0..args.warmup {
+                                implementation.run_any(&pool, reps).await?;
+                        }
+                        let mut runs: Vec<RunCost> = Vec::with_capacity(args.iterations as usize);
+                        for _ in 0..args.iterations {
+                                let (_df, cost) = implementation.run_any(&pool, reps).await?;
+                                runs.push(cost);
+                        }
+                        let summary = summarize(&runs);
+                        eprintln!(
+                                "{implementation} ×{reps}: {:.2} ± {:.2} ms (fetch {:.2}, transpose {:.2})",
+                                summary.total.millis.mean,
+                                summary.total.millis.std_dev,
+                                summary.fetch.millis.mean,
+                                summary.transpose.millis.mean
+                        );
+                        let rows = runs[0].rows;
+                        results.push(implementation, reps, rows, "fetch", summary.fetch);
+                        results.push(implementation, reps, rows, "transpose", summary.transpose);
+                        results.push(implementation, reps, rows, "total", summary.total);
+                }
+        }
+
+        let mut df = results.into_dataframe(&current_commit(), args.iterations)?;
+        println!("{df}");
+        if let Some(path) = args.output.write(&mut df)? {
+                println!("wrote {}", path.display());
+        }
+        Ok(())
+}
@@ -3,17 +3,19 @@
 
 use std::time::Instant;
 
-use clap::{Parser, ValueEnum};
+use clap::Parser;
 use derive_more::{Constructor, Display};
-use xp_sqlx::stream_to_df::{series_to_dataframe, struct_of_v_macro, v_of_struct_macro};
+use xp_sqlx::{bench::TransImpl, connection::ConnectionArgs, error::Error, export::OutputArgs, telemetry::TelemetryArgs};
 
 /// Arguments to select MemoryTranspose Implementations and Repetition of DB draws (increasing data transposed)
 /// Principally for use with Hyperfine to do benchmarking.
-/// (Preferred benchmarking framework (Divan) does not currently support async operations.)
+/// (Preferred benchmarking framework (Divan) does not currently support async operations;
+/// see the `transpose_bench` bin for in-process timings.)
 ///
 /// Note: This requires an active DataBase on a specific port.
 /// Justfile in the associatted repo has docker create and destroy code to set one up.
 /// `template.env` has default address for the dockerized DB
+/// (or, without docker: `--database-url sqlite::memory: --seed`)
 #[derive(Parser, Debug)]
 #[command(version, about)]
 struct Args {
@@ -21,16 +23,15 @@ struct Args {
         implementation: TransImpl,
         /// Number of times to repeat the test
         repetition:     u32,
+        #[command(flatten)]
+        output:         OutputArgs,
+        #[command(flatten)]
+        connection:     ConnectionArgs,
+        #[command(flatten)]
+        telemetry:      TelemetryArgs,
 }
 
-#[derive(ValueEnum, Clone, Debug)]
-enum TransImpl {
-        StructOfV,
-        // set to 'v-struct', but not worth hunting for more syntax to get rename = "lower" to work
-        VOfStruct,
-        SeriesToDF,
-}
-
+#[allow(dead_code)]
 #[derive(Debug, Constructor, Display)]
 #[display(
         "Vec<Struct>_macro! impl time: {}\nStruct<Vec<field>...>_macro! impl time: {}",
@@ -43,18 +44,21 @@ struct TimesTaken {
 }
 
 #[tokio::main]
-async fn main() -> Result<(), sqlx::Error> {
+async fn main() -> Result<(), Error> {
         let args = Args::parse();
+        let _telemetry = args.telemetry.init("transpose_implementations")?;
         let reps = args.repetition;
+        let pool = args.connection.connect_any().await.map_err(Error::Connection)?;
 
         let now = Instant::now();
-        match args.implementation {
-                TransImpl::VOfStruct => v_of_struct_macro(reps).await?,
-                TransImpl::StructOfV => struct_of_v_macro(reps).await?,
-                TransImpl::SeriesToDF => series_to_dataframe(reps).await?,
-        };
-
+        let (mut df, cost) = args.implementation.run_any(&pool, reps).await?;
         let elapsed_time = now.elapsed();
+
+        println!("{:?}", df);
+        if let Some(path) = args.output.write(&mut df)? {
+                println!("written to: {}", path.display());
+        }
+        println!("\n\n{} rows; fetch: {:?}, transpose: {:?}", cost.rows, cost.fetch.time, cost.transpose.time);
         println!("\n\nTotal Time Recorded (ms):\n{:#?}", elapsed_time.as_millis());
         Ok(())
 }
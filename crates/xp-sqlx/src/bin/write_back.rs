@@ -0,0 +1,50 @@
+//! Round trip: MySQL ~~> DataFrame ~~> MySQL
+//!
+//! Reads `enrollments`, marks ungraded rows `Incomplete`, and upserts the frame back.
+//! Re-running is harmless: rows already graded are written with their own values.
+
+use clap::Parser;
+use polars::prelude::*;
+use xp_sqlx::{connection::ConnectionArgs,
+              df_to_mysql::{OnDuplicate, WriteOptions, write_dataframe},
+              export::OutputArgs,
+              stream_to_df::sql_to_dataframe,
+              telemetry::TelemetryArgs};
+
+/// Arguments for clap
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+        #[command(flatten)]
+        output:     OutputArgs,
+        #[command(flatten)]
+        connection: ConnectionArgs,
+        #[command(flatten)]
+        telemetry:  TelemetryArgs,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+        let args = Args::parse();
+        let _telemetry = args.telemetry.init("write_back")?;
+        let pool = args.connection.connect().await?;
+
+        let df = sql_to_dataframe(&pool, "SELECT * FROM enrollments").await?;
+        println!("read:\n{df}");
+
+        let mut df = df
+                .lazy()
+                .with_column(col("Grade").fill_null(lit("Incomplete")))
+                .collect()?;
+        println!("to write:\n{df}");
+
+        let options =
+                WriteOptions { on_duplicate: OnDuplicate::Update(vec!["Grade".to_string()]), ..Default::default() };
+        let affected = write_dataframe(&pool, "enrollments", &df, &options).await?;
+        println!("rows affected: {affected}");
+        if let Some(path) = args.output.write(&mut df)? {
+                println!("written to: {}", path.display());
+        }
+
+        Ok(())
+}
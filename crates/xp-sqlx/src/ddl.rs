@@ -0,0 +1,835 @@
+//! `CREATE TABLE` statements ~~> the `schema` model, without a server
+//!
+//! Reads a MySQL dump like `data/db_gen/database_students.sql`: column types (with arguments, `UNSIGNED`, `ENUM(...)`),
+//! `NOT NULL`/`NULL`, `DEFAULT`, `AUTO_INCREMENT`, `COMMENT`, inline or table `PRIMARY KEY`, `UNIQUE`/`KEY`/`INDEX`
+//! and `[CONSTRAINT name] FOREIGN KEY (...) REFERENCES table (...) [ON DELETE/UPDATE ...]`.
+//! `USE` (else `CREATE DATABASE`) names the database; other statements (`INSERT`, ...) are skipped.
+//!
+//! The result is what `Schema::load` reads back from a MySQL 8 server that ran the same file:
+//! - types spelled as `COLUMN_TYPE` spells them (`INT(11)` ~~> `int`, `BOOLEAN` ~~> `tinyint(1)`,
+//!   `ENUM('a', 'b')` ~~> `enum('a','b')`)
+//! - primary key columns are `NOT NULL`
+//! - unnamed foreign keys are `<table>_ibfk_<n>`, rules default to `NO ACTION` (`DEFAULT_REFERENCE_RULE`),
+//!   and a foreign key without an index starting with its columns gets one (named after the constraint, else the
+//!   first column)
+//! - tables, foreign keys & indexes sorted by name, as the `information_schema` queries sort them
+
+use std::fmt;
+
+use derive_more::Display;
+
+use crate::schema::{Column, DEFAULT_REFERENCE_RULE, ForeignKey, Index, Schema, Table};
+
+/// A statement that didn't parse
+#[derive(Debug, Display, Clone, PartialEq)]
+#[display("line {line}: {message}")]
+pub struct DdlError {
+        pub line:    usize,
+        pub message: String,
+}
+impl std::error::Error for DdlError {}
+
+impl Schema {
+        /// The tables `sql` creates
+        pub fn from_ddl(sql: &str) -> Result<Self, DdlError> {
+                let tokens = tokenize(sql)?;
+                let mut database = None;
+                let mut created_database = None;
+                let mut tables = Vec::new();
+                for statement in tokens.split(|t| t.token == Token::Punct(';')) {
+                        let mut p = Parser { tokens: statement, pos: 0 };
+                        if p.eat_kw("USE") {
+                                database = Some(p.ident()?);
+                        } else if p.eat_kw("CREATE") {
+                                p.eat_kw("TEMPORARY");
+                                if p.eat_kw("DATABASE") || p.eat_kw("SCHEMA") {
+                                        p.eat_if_not_exists();
+                                        created_database = Some(p.ident()?);
+                                } else if p.eat_kw("TABLE") {
+                                        p.eat_if_not_exists();
+                                        tables.push(p.table()?);
+                                }
+                        }
+                }
+                tables.sort_by(|a: &Table, b| a.name.cmp(&b.name));
+                Ok(Self { database: database.or(created_database).unwrap_or_default(), tables })
+        }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+        /// keyword, unquoted identifier or number
+        Word(String),
+        /// `identifier`
+        Quoted(String),
+        /// 'text' or "text"
+        Str(String),
+        Punct(char),
+}
+
+impl fmt::Display for Token {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                        Self::Word(w) => write!(f, "`{w}`"),
+                        Self::Quoted(q) => write!(f, "`{q}`"),
+                        Self::Str(s) => write!(f, "'{s}'"),
+                        Self::Punct(c) => write!(f, "`{c}`"),
+                }
+        }
+}
+
+#[derive(Debug, Clone)]
+struct Spanned {
+        token: Token,
+        line:  usize,
+}
+
+/// Comments dropped, quotes resolved (`''`, `\'` & ``` `` ``` escapes)
+fn tokenize(sql: &str) -> Result<Vec<Spanned>, DdlError> {
+        let mut tokens = Vec::new();
+        let mut chars = sql.chars().peekable();
+        let mut line = 1;
+        while let Some(c) = chars.next() {
+                let start = line;
+                let token = match c {
+                        '\n' => {
+                                line += 1;
+                                continue;
+                        }
+                        c if c.is_whitespace() => continue,
+                        // MySQL wants a space (or control character) after `--`: `a--1` is `a - -1`
+                        '-' if chars.peek() == Some(&'-')
+                                && chars.clone().nth(1).is_none_or(|c| c.is_whitespace() || c.is_control()) =>
+                        {
+                                chars.by_ref().take_while(|&c| c != '\n').for_each(drop);
+                                line += 1;
+                                continue;
+                        }
+                        '#' => {
+                                chars.by_ref().take_while(|&c| c != '\n').for_each(drop);
+                                line += 1;
+                                continue;
+                        }
+                        '/' if chars.peek() == Some(&'*') => {
+                                chars.next();
+                                let mut previous = ' ';
+                                loop {
+                                        match chars.next() {
+                                                None => {
+                                                        return Err(DdlError {
+                                                                line:    start,
+                                                                message: "unterminated comment".into(),
+                                                        });
+                                                }
+                                                Some('/') if previous == '*' => break,
+                                                Some(c) => {
+                                                        line += usize::from(c == '\n');
+                                                        previous = c;
+                                                }
+                                        }
+                                }
+                                continue;
+                        }
+                        quote @ ('\'' | '"' | '`') => {
+                                let mut text = String::new();
+                                loop {
+                                        match chars.next() {
+                                                None => {
+                                                        return Err(DdlError {
+                                                                line:    start,
+                                                                message: format!("unterminated {quote}"),
+                                                        });
+                                                }
+                                                Some(c) if c == quote && chars.peek() == Some(&quote) => {
+                                                        chars.next();
+                                                        text.push(quote);
+                                                }
+                                                Some(c) if c == quote => break,
+                                                Some('\\') if quote != '`' => text.extend(chars.next()),
+                                                Some(c) => {
+                                                        line += usize::from(c == '\n');
+                                                        text.push(c);
+                                                }
+                                        }
+                                }
+                                if quote == '`' { Token::Quoted(text) } else { Token::Str(text) }
+                        }
+                        c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                                let mut word = c.to_string();
+                                while let Some(&c) = chars.peek() {
+                                        if !(c.is_alphanumeric() || c == '_' || c == '$' || c == '.') {
+                                                break;
+                                        }
+                                        word.push(c);
+                                        chars.next();
+                                }
+                                Token::Word(word)
+                        }
+                        c => Token::Punct(c),
+                };
+                tokens.push(Spanned { token, line: start });
+        }
+        Ok(tokens)
+}
+
+/// One statement's tokens
+struct Parser<'t> {
+        tokens: &'t [Spanned],
+        pos:    usize,
+}
+impl Parser<'_> {
+        fn peek(&self) -> Option<&Token> {
+                self.tokens.get(self.pos).map(|t| &t.token)
+        }
+
+        fn line(&self) -> usize {
+                self.tokens.get(self.pos).or(self.tokens.last()).map_or(0, |t| t.line)
+        }
+
+        fn error<T>(&self, message: impl Into<String>) -> Result<T, DdlError> {
+                Err(DdlError { line: self.line(), message: message.into() })
+        }
+
+        fn is_kw(&self, keyword: &str) -> bool {
+                matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+        }
+
+        fn eat_kw(&mut self, keyword: &str) -> bool {
+                let found = self.is_kw(keyword);
+                self.pos += usize::from(found);
+                found
+        }
+
+        fn expect_kw(&mut self, keyword: &str) -> Result<(), DdlError> {
+                if self.eat_kw(keyword) { Ok(()) } else { self.error(format!("expected `{keyword}`")) }
+        }
+
+        fn eat_punct(&mut self, punct: char) -> bool {
+                let found = self.peek() == Some(&Token::Punct(punct));
+                self.pos += usize::from(found);
+                found
+        }
+
+        fn expect_punct(&mut self, punct: char) -> Result<(), DdlError> {
+                if self.eat_punct(punct) { Ok(()) } else { self.error(format!("expected `{punct}`")) }
+        }
+
+        fn eat_if_not_exists(&mut self) {
+                if self.is_kw("IF") {
+                        self.pos += 3;
+                }
+        }
+
+        /// A (possibly quoted) name; `db.name` is `name`
+        fn ident(&mut self) -> Result<String, DdlError> {
+                let name = match self.peek() {
+                        Some(Token::Word(w)) => w.rsplit('.').next().unwrap_or(w).to_string(),
+                        Some(Token::Quoted(q)) => q.clone(),
+                        _ => return self.error("expected a name"),
+                };
+                self.pos += 1;
+                // `db`.`name`
+                if self.peek() == Some(&Token::Punct('.')) {
+                        self.pos += 1;
+                        return self.ident();
+                }
+                Ok(name)
+        }
+
+        fn string(&mut self) -> Result<String, DdlError> {
+                match self.peek() {
+                        Some(Token::Str(s)) => {
+                                let s = s.clone();
+                                self.pos += 1;
+                                Ok(s)
+                        }
+                        _ => self.error("expected a quoted string"),
+                }
+        }
+
+        /// Skips a balanced `( ... )`, if one is next
+        fn skip_parens(&mut self) {
+                if !self.eat_punct('(') {
+                        return;
+                }
+                let mut depth = 1;
+                while depth > 0 {
+                        match self.peek() {
+                                None => return,
+                                Some(Token::Punct('(')) => depth += 1,
+                                Some(Token::Punct(')')) => depth -= 1,
+                                _ => {}
+                        }
+                        self.pos += 1;
+                }
+        }
+
+        /// Index options (`USING BTREE`, `COMMENT '...'`, `INVISIBLE`, `KEY_BLOCK_SIZE = 8`, ...), up to the `,` or `)`
+        /// ending the definition
+        fn skip_index_options(&mut self) {
+                while !matches!(self.peek(), None | Some(Token::Punct(',' | ')'))) {
+                        if matches!(self.peek(), Some(Token::Punct('('))) {
+                                self.skip_parens();
+                        } else {
+                                self.pos += 1;
+                        }
+                }
+        }
+
+        /// `(a, b(10) DESC, ...)` ~~> `[a, b]`
+        fn key_columns(&mut self) -> Result<Vec<String>, DdlError> {
+                self.expect_punct('(')?;
+                let mut columns = Vec::new();
+                loop {
+                        columns.push(self.ident()?);
+                        // prefix length, ASC/DESC
+                        self.skip_parens();
+                        let _ = self.eat_kw("ASC") || self.eat_kw("DESC");
+                        if self.eat_punct(')') {
+                                return Ok(columns);
+                        }
+                        self.expect_punct(',')?;
+                }
+        }
+
+        /// `CREATE TABLE` after the keywords: name, definitions, options
+        fn table(&mut self) -> Result<Table, DdlError> {
+                let mut builder =
+                        TableBuilder { table: Table::new(self.ident()?, None), foreign_keys: Vec::new() };
+                if self.is_kw("LIKE") {
+                        return self.error("`CREATE TABLE ... LIKE` isn't supported");
+                }
+                self.expect_punct('(')?;
+                loop {
+                        self.definition(&mut builder)?;
+                        if self.eat_punct(')') {
+                                break;
+                        }
+                        self.expect_punct(',')?;
+                }
+                // table options: ENGINE=InnoDB COMMENT='...' ...
+                while self.peek().is_some() {
+                        if self.eat_kw("COMMENT") {
+                                self.eat_punct('=');
+                                builder.table.comment = Some(self.string()?).filter(|c| !c.is_empty());
+                        } else {
+                                self.pos += 1;
+                        }
+                }
+                Ok(builder.finish())
+        }
+
+        /// One comma separated part of the table body
+        fn definition(&mut self, builder: &mut TableBuilder) -> Result<(), DdlError> {
+                let symbol = match self.eat_kw("CONSTRAINT") {
+                        true if !(self.is_kw("PRIMARY") || self.is_kw("UNIQUE") || self.is_kw("FOREIGN")) => {
+                                Some(self.ident()?)
+                        }
+                        _ => None,
+                };
+                if self.eat_kw("PRIMARY") {
+                        self.expect_kw("KEY")?;
+                        builder.table.primary_key = self.key_columns()?;
+                        self.skip_index_options();
+                } else if self.eat_kw("FOREIGN") {
+                        self.expect_kw("KEY")?;
+                        let index_name = match self.peek() {
+                                Some(Token::Punct('(')) => None,
+                                _ => Some(self.ident()?),
+                        };
+                        let columns = self.key_columns()?;
+                        self.expect_kw("REFERENCES")?;
+                        let referenced_table = self.ident()?;
+                        let referenced_columns = self.key_columns()?;
+                        if columns.len() != referenced_columns.len() {
+                                return self.error("foreign key column counts differ");
+                        }
+                        let (mut on_delete, mut on_update) =
+                                (DEFAULT_REFERENCE_RULE.to_string(), DEFAULT_REFERENCE_RULE.to_string());
+                        while self.eat_kw("ON") {
+                                let rule = if self.eat_kw("DELETE") {
+                                        &mut on_delete
+                                } else {
+                                        self.expect_kw("UPDATE")?;
+                                        &mut on_update
+                                };
+                                *rule = self.reference_option()?;
+                        }
+                        builder.foreign_keys.push(PendingForeignKey {
+                                symbol,
+                                index_name,
+                                key: ForeignKey {
+                                        name: String::new(),
+                                        columns,
+                                        referenced_table,
+                                        referenced_columns,
+                                        on_update,
+                                        on_delete,
+                                },
+                        });
+                } else if self.eat_kw("UNIQUE") {
+                        let _ = self.eat_kw("KEY") || self.eat_kw("INDEX");
+                        self.index(builder, symbol, true)?;
+                } else if self.eat_kw("KEY") || self.eat_kw("INDEX") {
+                        self.index(builder, None, false)?;
+                } else if self.eat_kw("FULLTEXT") || self.eat_kw("SPATIAL") {
+                        let _ = self.eat_kw("KEY") || self.eat_kw("INDEX");
+                        self.index(builder, None, false)?;
+                } else if self.eat_kw("CHECK") {
+                        self.skip_parens();
+                        let _ = self.eat_kw("NOT");
+                        self.eat_kw("ENFORCED");
+                } else {
+                        self.column(builder)?;
+                }
+                Ok(())
+        }
+
+        /// `RESTRICT`, `CASCADE`, `SET NULL`, `SET DEFAULT` or `NO ACTION`
+        fn reference_option(&mut self) -> Result<String, DdlError> {
+                for option in ["RESTRICT", "CASCADE"] {
+                        if self.eat_kw(option) {
+                                return Ok(option.to_string());
+                        }
+                }
+                if self.eat_kw("SET") {
+                        for option in ["NULL", "DEFAULT"] {
+                                if self.eat_kw(option) {
+                                        return Ok(format!("SET {option}"));
+                                }
+                        }
+                } else if self.eat_kw("NO") {
+                        self.expect_kw("ACTION")?;
+                        return Ok("NO ACTION".to_string());
+                }
+                self.error("expected RESTRICT, CASCADE, SET NULL, SET DEFAULT or NO ACTION")
+        }
+
+        /// `[name] (columns)` of a `KEY`/`INDEX`/`UNIQUE`, after the keywords
+        fn index(&mut self, builder: &mut TableBuilder, symbol: Option<String>, unique: bool) -> Result<(), DdlError> {
+                let name = match self.peek() {
+                        Some(Token::Punct('(')) => symbol,
+                        _ if self.is_kw("USING") => symbol,
+                        _ => Some(self.ident()?),
+                };
+                if self.eat_kw("USING") {
+                        self.pos += 1;
+                }
+                let columns = self.key_columns()?;
+                self.skip_index_options();
+                builder.add_index(name, columns, unique);
+                Ok(())
+        }
+
+        /// `name type[(args)] [UNSIGNED] [NOT NULL] [DEFAULT x] [AUTO_INCREMENT] ...`
+        fn column(&mut self, builder: &mut TableBuilder) -> Result<(), DdlError> {
+                let name = self.ident()?;
+                let Some(Token::Word(type_name)) = self.peek().cloned() else {
+                        return self.error(format!("expected a type for `{name}`"));
+                };
+                self.pos += 1;
+                if type_name.eq_ignore_ascii_case("DOUBLE") {
+                        self.eat_kw("PRECISION");
+                }
+                let args = self.type_args()?;
+                let (mut unsigned, mut zerofill) = (false, false);
+                let mut column = Column {
+                        name:           name.clone(),
+                        data_type:      String::new(),
+                        column_type:    String::new(),
+                        nullable:       true,
+                        default:        None,
+                        auto_increment: false,
+                        comment:        None,
+                };
+                while let Some(token) = self.peek().cloned() {
+                        if matches!(token, Token::Punct(',' | ')')) {
+                                break;
+                        }
+                        if self.eat_kw("UNSIGNED") {
+                                unsigned = true;
+                        } else if self.eat_kw("SIGNED") {
+                        } else if self.eat_kw("ZEROFILL") {
+                                zerofill = true;
+                        } else if self.eat_kw("NOT") {
+                                self.expect_kw("NULL")?;
+                                column.nullable = false;
+                        } else if self.eat_kw("NULL") {
+                                column.nullable = true;
+                        } else if self.eat_kw("DEFAULT") {
+                                column.default = self.default_value()?;
+                        } else if self.eat_kw("AUTO_INCREMENT") {
+                                column.auto_increment = true;
+                        } else if self.eat_kw("COMMENT") {
+                                column.comment = Some(self.string()?).filter(|c| !c.is_empty());
+                        } else if self.eat_kw("PRIMARY") {
+                                self.expect_kw("KEY")?;
+                                builder.table.primary_key = vec![name.clone()];
+                        } else if self.eat_kw("KEY") {
+                                builder.table.primary_key = vec![name.clone()];
+                        } else if self.eat_kw("UNIQUE") {
+                                self.eat_kw("KEY");
+                                builder.add_index(None, vec![name.clone()], true);
+                        } else if self.eat_kw("CHARACTER") {
+                                self.expect_kw("SET")?;
+                                self.ident()?;
+                        } else if self.eat_kw("CHARSET") || self.eat_kw("COLLATE") {
+                                self.ident()?;
+                        } else if self.eat_kw("ON") {
+                                // ON UPDATE CURRENT_TIMESTAMP[(fsp)]
+                                self.expect_kw("UPDATE")?;
+                                self.ident()?;
+                                self.skip_parens();
+                        } else if self.eat_kw("REFERENCES") {
+                                // inline references are parsed, and ignored, by MySQL
+                                self.ident()?;
+                                self.skip_parens();
+                                while self.eat_kw("ON") {
+                                        self.pos += 1;
+                                        self.reference_option()?;
+                                }
+                        } else if self.eat_kw("VISIBLE") || self.eat_kw("INVISIBLE") {
+                        } else {
+                                return self.error(format!("unexpected {token} in column `{name}`"));
+                        }
+                }
+                (column.data_type, column.column_type) = column_type(&type_name, &args, unsigned, zerofill);
+                builder.table.columns.push(column);
+                Ok(())
+        }
+
+        /// `(10)`, `(10, 2)` or `('a', 'b')`, if present
+        fn type_args(&mut self) -> Result<Vec<Token>, DdlError> {
+                let mut args = Vec::new();
+                if !self.eat_punct('(') {
+                        return Ok(args);
+                }
+                loop {
+                        match self.peek().cloned() {
+                                Some(Token::Punct(')')) => {
+                                        self.pos += 1;
+                                        return Ok(args);
+                                }
+                                Some(Token::Punct(',')) => self.pos += 1,
+                                Some(token @ (Token::Word(_) | Token::Str(_))) => {
+                                        args.push(token);
+                                        self.pos += 1;
+                                }
+                                _ => return self.error("unexpected token in type arguments"),
+                        }
+                }
+        }
+
+        /// As `COLUMN_DEFAULT` reports it: unquoted text, `None` for `NULL`
+        fn default_value(&mut self) -> Result<Option<String>, DdlError> {
+                let value = match self.peek().cloned() {
+                        Some(Token::Str(s)) => Some(s),
+                        Some(Token::Word(w)) if w.eq_ignore_ascii_case("NULL") => None,
+                        Some(Token::Word(w)) if w.eq_ignore_ascii_case("TRUE") => Some("1".to_string()),
+                        Some(Token::Word(w)) if w.eq_ignore_ascii_case("FALSE") => Some("0".to_string()),
+                        Some(Token::Word(w)) => {
+                                self.pos += 1;
+                                // CURRENT_TIMESTAMP(3)
+                                self.skip_parens();
+                                return Ok(Some(w));
+                        }
+                        Some(Token::Punct(sign @ ('-' | '+'))) => {
+                                self.pos += 1;
+                                let Some(Token::Word(number)) = self.peek().cloned() else {
+                                        return self.error("expected a number after the sign");
+                                };
+                                Some(if sign == '-' { format!("-{number}") } else { number })
+                        }
+                        Some(Token::Punct('(')) => {
+                                let start = self.pos;
+                                self.skip_parens();
+                                let text: Vec<_> = self.tokens[start + 1..self.pos - 1]
+                                        .iter()
+                                        .map(|t| match &t.token {
+                                                Token::Word(w) => w.clone(),
+                                                Token::Quoted(q) => format!("`{q}`"),
+                                                Token::Str(s) => format!("'{}'", s.replace('\'', "\\'")),
+                                                Token::Punct(c) => c.to_string(),
+                                        })
+                                        .collect();
+                                return Ok(Some(text.join(" ")));
+                        }
+                        _ => return self.error("expected a default value"),
+                };
+                self.pos += 1;
+                Ok(value)
+        }
+}
+
+/// `(DATA_TYPE, COLUMN_TYPE)` MySQL 8 reports for a declared type
+fn column_type(type_name: &str, args: &[Token], unsigned: bool, zerofill: bool) -> (String, String) {
+        let lower = type_name.to_ascii_lowercase();
+        let base = match lower.as_str() {
+                "integer" | "int4" => "int",
+                "int1" => "tinyint",
+                "int2" => "smallint",
+                "int3" | "middleint" => "mediumint",
+                "int8" => "bigint",
+                "bool" | "boolean" => return ("tinyint".to_string(), "tinyint(1)".to_string()),
+                "dec" | "numeric" | "fixed" => "decimal",
+                "real" => "double",
+                "character" => "char",
+                other => other,
+        };
+        let words: Vec<&str> = args
+                .iter()
+                .filter_map(|a| match a {
+                        Token::Word(w) => Some(w.as_str()),
+                        _ => None,
+                })
+                .collect();
+        let args = match base {
+                "enum" | "set" => {
+                        let values: Vec<_> = args
+                                .iter()
+                                .filter_map(|a| match a {
+                                        Token::Str(s) => Some(format!("'{}'", s.replace('\'', "''"))),
+                                        _ => None,
+                                })
+                                .collect();
+                        format!("({})", values.join(","))
+                }
+                // display widths are gone since 8.0.19, except `tinyint(1)` & with ZEROFILL
+                "tinyint" if words == ["1"] => "(1)".to_string(),
+                "tinyint" | "smallint" | "mediumint" | "int" | "bigint" if !zerofill => String::new(),
+                "decimal" => match words.as_slice() {
+                        [] => "(10,0)".to_string(),
+                        [precision] => format!("({precision},0)"),
+                        _ => format!("({})", words.join(",")),
+                },
+                "char" | "binary" if words.is_empty() => "(1)".to_string(),
+                _ if words.is_empty() => String::new(),
+                _ => format!("({})", words.join(",")),
+        };
+        let mut column_type = format!("{base}{args}");
+        if unsigned || zerofill {
+                column_type.push_str(" unsigned");
+        }
+        if zerofill {
+                column_type.push_str(" zerofill");
+        }
+        (base.to_string(), column_type)
+}
+
+struct TableBuilder {
+        table:        Table,
+        foreign_keys: Vec<PendingForeignKey>,
+}
+
+/// A foreign key before its name (and index) are settled
+struct PendingForeignKey {
+        symbol:     Option<String>,
+        index_name: Option<String>,
+        key:        ForeignKey,
+}
+
+impl TableBuilder {
+        /// An unnamed index is named after its first column (`col_2`, ... if taken)
+        fn add_index(&mut self, name: Option<String>, columns: Vec<String>, unique: bool) {
+                let name = name.unwrap_or_else(|| {
+                        let first = columns.first().cloned().unwrap_or_default();
+                        let mut name = first.clone();
+                        let mut n = 2;
+                        while self.table.indexes.iter().any(|i| i.name.eq_ignore_ascii_case(&name)) {
+                                name = format!("{first}_{n}");
+                                n += 1;
+                        }
+                        name
+                });
+                self.table.indexes.push(Index { name, columns, unique });
+        }
+
+        fn finish(mut self) -> Table {
+                let name = self.table.name.clone();
+                for column in &mut self.table.columns {
+                        if self.table
+                                .primary_key
+                                .iter()
+                                .any(|c| c.eq_ignore_ascii_case(&column.name))
+                        {
+                                column.nullable = false;
+                        }
+                }
+                if !self.table.primary_key.is_empty() {
+                        let columns = self.table.primary_key.clone();
+                        self.table
+                                .indexes
+                                .push(Index { name: "PRIMARY".to_string(), columns, unique: true });
+                }
+                let mut generated = 0;
+                for pending in std::mem::take(&mut self.foreign_keys) {
+                        let mut key = pending.key;
+                        key.name = pending.symbol.clone().unwrap_or_else(|| {
+                                generated += 1;
+                                format!("{name}_ibfk_{generated}")
+                        });
+                        let covered = self.table.indexes.iter().any(|index| {
+                                index.columns.len() >= key.columns.len()
+                                        && index.columns
+                                                .iter()
+                                                .zip(&key.columns)
+                                                .all(|(a, b)| a.eq_ignore_ascii_case(b))
+                        });
+                        if !covered {
+                                self.add_index(pending.symbol.or(pending.index_name), key.columns.clone(), false);
+                        }
+                        self.table.foreign_keys.push(key);
+                }
+                let by_name = |a: &str, b: &str| a.to_lowercase().cmp(&b.to_lowercase()).then_with(|| a.cmp(b));
+                self.table.foreign_keys.sort_by(|a, b| by_name(&a.name, &b.name));
+                self.table.indexes.sort_by(|a, b| by_name(&a.name, &b.name));
+                self.table
+        }
+}
+
+#[cfg(test)]
+mod tests {
+        use super::*;
+
+        /// MySQL DDL & seed data of the demo `university` database
+        const STUDENTS_SQL: &str = include_str!("../../../data/db_gen/database_students.sql");
+
+        fn students_schema() -> Schema {
+                Schema::from_ddl(STUDENTS_SQL).expect("the demo DDL parses")
+        }
+
+        fn index(name: &str, columns: &[&str], unique: bool) -> Index {
+                Index { name: name.to_string(), columns: columns.iter().map(|c| c.to_string()).collect(), unique }
+        }
+
+        #[test]
+        fn demo_tables_in_name_order() {
+                let schema = students_schema();
+                assert_eq!(schema.database, "university");
+                let names: Vec<_> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+                assert_eq!(names, ["courses", "enrollments", "professors", "students"]);
+        }
+
+        #[test]
+        fn columns_with_types_and_nullability() {
+                let schema = students_schema();
+                let students = schema.table("students").unwrap();
+                let columns: Vec<_> = students
+                        .columns
+                        .iter()
+                        .map(|c| (c.name.as_str(), c.data_type.as_str(), c.column_type.as_str(), c.nullable))
+                        .collect();
+                assert_eq!(columns, [
+                        ("StudentID", "int", "int", false),
+                        ("FirstName", "varchar", "varchar(100)", false),
+                        ("LastName", "varchar", "varchar(100)", false),
+                        ("DateOfBirth", "date", "date", true),
+                        ("School", "enum", "enum('Sciences','Humanities','Other')", true),
+                        ("Email", "varchar", "varchar(255)", true),
+                ]);
+                let id = students.column("StudentID").unwrap();
+                assert!(id.auto_increment);
+                assert_eq!(id.default, None);
+                assert_eq!(students.primary_key, ["StudentID"]);
+        }
+
+        #[test]
+        fn enum_values_as_declared() {
+                let schema = students_schema();
+                let grade = schema.table("enrollments").unwrap().column("Grade").unwrap();
+                assert_eq!(grade.column_type, "enum('A','B','C','D','F','Incomplete')");
+                let school = schema.table("students").unwrap().column("School").unwrap();
+                assert_eq!(crate::enums::parse_enum_values(&school.column_type).unwrap(), [
+                        "Sciences",
+                        "Humanities",
+                        "Other"
+                ]);
+        }
+
+        #[test]
+        fn unnamed_foreign_keys_get_ibfk_names_and_indexes() {
+                let schema = students_schema();
+                let enrollments = schema.table("enrollments").unwrap();
+                let keys: Vec<_> = enrollments
+                        .foreign_keys
+                        .iter()
+                        .map(|fk| (fk.name.as_str(), fk.columns.as_slice(), fk.referenced_table.as_str()))
+                        .collect();
+                assert_eq!(keys, [
+                        ("enrollments_ibfk_1", ["StudentID".to_string()].as_slice(), "students"),
+                        ("enrollments_ibfk_2", ["CourseID".to_string()].as_slice(), "courses"),
+                ]);
+                for fk in &enrollments.foreign_keys {
+                        assert_eq!(fk.referenced_columns, fk.columns);
+                        assert_eq!((fk.on_update.as_str(), fk.on_delete.as_str()), ("NO ACTION", "NO ACTION"));
+                }
+                assert_eq!(enrollments.indexes, [
+                        index("CourseID", &["CourseID"], false),
+                        index("PRIMARY", &["EnrollmentID"], true),
+                        index("StudentID", &["StudentID"], false),
+                ]);
+                for name in ["courses", "professors", "students"] {
+                        let table = schema.table(name).unwrap();
+                        assert!(table.foreign_keys.is_empty(), "{name}");
+                        assert_eq!(table.indexes.len(), 1, "{name}");
+                        assert_eq!(table.indexes[0].name, "PRIMARY", "{name}");
+                }
+        }
+
+        #[test]
+        fn named_constraints_rules_and_covering_indexes() {
+                let schema = Schema::from_ddl(
+                        "CREATE TABLE t (
+                            a INT, b INT,
+                            KEY ab (a, b),
+                            CONSTRAINT t_a FOREIGN KEY (a) REFERENCES p (id) ON DELETE CASCADE,
+                            FOREIGN KEY (b) REFERENCES p (id) ON UPDATE SET NULL
+                        )",
+                )
+                .unwrap();
+                let t = schema.table("t").unwrap();
+                let keys: Vec<_> = t
+                        .foreign_keys
+                        .iter()
+                        .map(|fk| (fk.name.as_str(), fk.on_update.as_str(), fk.on_delete.as_str()))
+                        .collect();
+                assert_eq!(keys, [("t_a", "NO ACTION", "CASCADE"), ("t_ibfk_1", "SET NULL", "NO ACTION")]);
+                // `ab` already starts with `a`; `b` needs one of its own
+                assert_eq!(t.indexes, [index("ab", &["a", "b"], false), index("b", &["b"], false)]);
+        }
+
+        #[test]
+        fn index_options_are_skipped() {
+                let schema = Schema::from_ddl(
+                        "CREATE TABLE t (
+                            a INT, b INT, c INT,
+                            PRIMARY KEY (a) USING BTREE,
+                            KEY b (b) USING HASH COMMENT 'by b, (mostly)' INVISIBLE,
+                            UNIQUE KEY c (c) KEY_BLOCK_SIZE = 8 VISIBLE
+                        )",
+                )
+                .unwrap();
+                let t = schema.table("t").unwrap();
+                assert_eq!(t.primary_key, ["a"]);
+                assert_eq!(t.indexes, [
+                        index("b", &["b"], false),
+                        index("c", &["c"], true),
+                        index("PRIMARY", &["a"], true)
+                ]);
+        }
+
+        #[test]
+        fn double_dash_needs_a_space_to_start_a_comment() {
+                let schema = Schema::from_ddl(
+                        "CREATE TABLE t (
+                            a INT,
+                            CHECK (a--1 > 0),
+                            --	dropped
+                            b INT --
+                        )",
+                )
+                .unwrap();
+                let t = schema.table("t").unwrap();
+                assert_eq!(t.columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+        }
+}
@@ -0,0 +1,266 @@
+//! Writing a polars::DataFrame back into a MySQL table
+//!
+//! Batched multi-row `INSERT ... VALUES (...), (...)` built with `sqlx::QueryBuilder`,
+//! split so no statement outgrows the server's `max_allowed_packet`,
+//! all inside one transaction (a failing batch leaves the table untouched).
+
+use std::borrow::Cow;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+use sqlx::{MySql, QueryBuilder, mysql::MySqlPool, query_builder::Separated};
+
+/// MySQL caps placeholders per prepared statement at u16::MAX
+const MAX_PLACEHOLDERS: usize = 65_535;
+/// share of `max_allowed_packet` we allow ourselves (rest is headroom for SQL text & protocol)
+const PACKET_HEADROOM: f64 = 0.9;
+/// rough per-value protocol overhead (type byte, length prefix, ...)
+const VALUE_OVERHEAD: usize = 4;
+
+/// What to do when a row hits an existing primary/unique key
+#[derive(Debug, Clone, Default)]
+pub enum OnDuplicate {
+        /// plain INSERT: the write fails (and rolls back)
+        #[default]
+        Error,
+        /// `ON DUPLICATE KEY UPDATE` every column to the incoming value
+        UpdateAll,
+        /// `ON DUPLICATE KEY UPDATE` only these columns
+        Update(Vec<String>),
+}
+
+/// Options for `write_dataframe`
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+        pub on_duplicate:     OnDuplicate,
+        /// bytes per statement; `None` asks the server for `@@max_allowed_packet`
+        pub max_packet_bytes: Option<usize>,
+}
+
+/// polars::DataType ~~> the MySQL column type it's written as
+/// `None` for dtypes we don't write (lists, structs, durations, ...)
+pub fn polars_to_mysql_type(dtype: &DataType) -> Option<&'static str> {
+        Some(match dtype {
+                DataType::Boolean => "BOOLEAN",
+                DataType::Int8 => "TINYINT",
+                DataType::Int16 => "SMALLINT",
+                DataType::Int32 => "INT",
+                DataType::Int64 => "BIGINT",
+                DataType::UInt8 => "TINYINT UNSIGNED",
+                DataType::UInt16 => "SMALLINT UNSIGNED",
+                DataType::UInt32 => "INT UNSIGNED",
+                DataType::UInt64 => "BIGINT UNSIGNED",
+                DataType::Float32 => "FLOAT",
+                DataType::Float64 => "DOUBLE",
+                DataType::String => "VARCHAR",
+                DataType::Categorical(..) | DataType::Enum(..) => "ENUM",
+                DataType::Binary => "BLOB",
+                DataType::Date => "DATE",
+                DataType::Datetime(..) => "DATETIME",
+                DataType::Null => "NULL",
+                _ => return None,
+        })
+}
+
+/// A single value on its way into a bind parameter
+#[derive(Debug)]
+enum SqlValue<'a> {
+        Null,
+        Bool(bool),
+        Int(i64),
+        UInt(u64),
+        Float(f64),
+        Text(Cow<'a, str>),
+        Binary(&'a [u8]),
+        Date(NaiveDate),
+        Datetime(NaiveDateTime),
+}
+impl<'a> SqlValue<'a> {
+        fn from_any(value: AnyValue<'a>, column: &str) -> Result<Self, sqlx::Error> {
+                Ok(match value {
+                        AnyValue::Null => Self::Null,
+                        AnyValue::Boolean(v) => Self::Bool(v),
+                        AnyValue::Int8(v) => Self::Int(v.into()),
+                        AnyValue::Int16(v) => Self::Int(v.into()),
+                        AnyValue::Int32(v) => Self::Int(v.into()),
+                        AnyValue::Int64(v) => Self::Int(v),
+                        AnyValue::UInt8(v) => Self::UInt(v.into()),
+                        AnyValue::UInt16(v) => Self::UInt(v.into()),
+                        AnyValue::UInt32(v) => Self::UInt(v.into()),
+                        AnyValue::UInt64(v) => Self::UInt(v),
+                        AnyValue::Float32(v) => Self::Float(v.into()),
+                        AnyValue::Float64(v) => Self::Float(v),
+                        AnyValue::String(v) => Self::Text(Cow::Borrowed(v)),
+                        AnyValue::StringOwned(v) => Self::Text(Cow::Owned(v.to_string())),
+                        ref cat @ (AnyValue::Categorical(..)
+                        | AnyValue::CategoricalOwned(..)
+                        | AnyValue::Enum(..)
+                        | AnyValue::EnumOwned(..)) => {
+                                Self::Text(Cow::Owned(cat.get_str().unwrap_or_default().to_string()))
+                        }
+                        AnyValue::Binary(v) => Self::Binary(v),
+                        AnyValue::Date(days) => {
+                                Self::Date(DateTime::UNIX_EPOCH.date_naive() + chrono::Duration::days(days.into()))
+                        }
+                        AnyValue::Datetime(v, unit, _) | AnyValue::DatetimeOwned(v, unit, _) => {
+                                let datetime = match unit {
+                                        TimeUnit::Nanoseconds => Some(DateTime::from_timestamp_nanos(v)),
+                                        TimeUnit::Microseconds => DateTime::from_timestamp_micros(v),
+                                        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(v),
+                                };
+                                Self::Datetime(
+                                        datetime.ok_or_else(|| {
+                                                sqlx::Error::Encode(
+                                                        format!("column `{column}`: datetime {v} out of range").into(),
+                                                )
+                                        })?
+                                        .naive_utc(),
+                                )
+                        }
+                        other => {
+                                return Err(sqlx::Error::Encode(
+                                        format!("column `{column}`: cannot write {} values to MySQL", other.dtype())
+                                                .into(),
+                                ));
+                        }
+                })
+        }
+
+        /// rough size on the wire
+        fn byte_estimate(&self) -> usize {
+                VALUE_OVERHEAD
+                        + match self {
+                                Self::Null => 0,
+                                Self::Bool(_) => 1,
+                                Self::Int(_) | Self::UInt(_) | Self::Float(_) => 8,
+                                Self::Text(v) => v.len(),
+                                Self::Binary(v) => v.len(),
+                                Self::Date(_) => 4,
+                                Self::Datetime(_) => 11,
+                        }
+        }
+
+        fn bind(self, tuple: &mut Separated<'_, 'a, MySql, &'static str>) {
+                match self {
+                        Self::Null => tuple.push_bind(None::<&str>),
+                        Self::Bool(v) => tuple.push_bind(v),
+                        Self::Int(v) => tuple.push_bind(v),
+                        Self::UInt(v) => tuple.push_bind(v),
+                        Self::Float(v) => tuple.push_bind(v),
+                        Self::Text(v) => tuple.push_bind(v),
+                        Self::Binary(v) => tuple.push_bind(v),
+                        Self::Date(v) => tuple.push_bind(v),
+                        Self::Datetime(v) => tuple.push_bind(v),
+                };
+        }
+}
+
+/// `name` ~~> `` `name` `` (backticks inside doubled)
+fn quote_ident(name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+}
+
+/// One `INSERT ... VALUES` statement for a batch of rows
+fn insert_statement<'a>(
+        table: &str,
+        columns: &[&str],
+        rows: Vec<Vec<SqlValue<'a>>>,
+        on_duplicate: &OnDuplicate,
+) -> QueryBuilder<'a, MySql> {
+        let column_list = columns.iter().map(|c| quote_ident(c)).collect::<Vec<_>>().join(", ");
+        let mut builder = QueryBuilder::new(format!("INSERT INTO {} ({}) ", quote_ident(table), column_list));
+        builder.push_values(rows, |mut tuple, row| {
+                for value in row {
+                        value.bind(&mut tuple);
+                }
+        });
+        let updates: Vec<_> = match on_duplicate {
+                OnDuplicate::Error => Vec::new(),
+                OnDuplicate::UpdateAll => columns.iter().map(|c| quote_ident(c)).collect(),
+                OnDuplicate::Update(cols) => cols.iter().map(|c| quote_ident(c)).collect(),
+        };
+        if !updates.is_empty() {
+                // row alias (MySQL 8.0.19+): `VALUES(col)` is deprecated
+                builder.push(" AS new ON DUPLICATE KEY UPDATE ");
+                builder.push(updates
+                        .iter()
+                        .map(|c| format!("{c} = new.{c}"))
+                        .collect::<Vec<_>>()
+                        .join(", "));
+        }
+        builder
+}
+
+/// Polars::DataFrame ~~> rows of an existing MySQL `table`
+///
+/// Frame columns are matched to table columns by name.
+/// Rows are sent as batched multi-row INSERTs, each kept under the packet budget
+/// (and MySQL's 65,535 placeholder limit), in a single transaction.
+/// Returns total rows affected (note: MySQL counts an upsert-updated row as 2).
+pub async fn write_dataframe(
+        pool: &MySqlPool,
+        table: &str,
+        df: &DataFrame,
+        options: &WriteOptions,
+) -> Result<u64, sqlx::Error> {
+        if df.width() == 0 || df.height() == 0 {
+                return Ok(0);
+        }
+        for col in df.get_columns() {
+                if polars_to_mysql_type(col.dtype()).is_none() {
+                        return Err(sqlx::Error::Encode(
+                                format!("column `{}`: no MySQL mapping for dtype {}", col.name(), col.dtype()).into(),
+                        ));
+                }
+        }
+        if let OnDuplicate::Update(cols) = &options.on_duplicate {
+                if let Some(missing) = cols.iter().find(|c| df.column(c).is_err()) {
+                        return Err(sqlx::Error::Encode(
+                                format!("upsert column `{missing}` is not in the DataFrame").into(),
+                        ));
+                }
+        }
+
+        let max_packet = match options.max_packet_bytes {
+                Some(bytes) => bytes,
+                None => {
+                        sqlx::query_scalar::<_, u64>("SELECT @@max_allowed_packet")
+                                .fetch_one(pool)
+                                .await? as usize
+                }
+        };
+        let byte_budget = (max_packet as f64 * PACKET_HEADROOM) as usize;
+        let max_rows = (MAX_PLACEHOLDERS / df.width()).max(1);
+
+        let names: Vec<&str> = df.get_column_names().into_iter().map(|n| n.as_str()).collect();
+        let series: Vec<&Series> = df.get_columns().iter().map(|c| c.as_materialized_series()).collect();
+
+        let mut tx = pool.begin().await?;
+        let mut affected = 0;
+        let mut batch: Vec<Vec<SqlValue>> = Vec::new();
+        let mut batch_bytes = 0;
+        for row in 0..df.height() {
+                let values = series
+                        .iter()
+                        .zip(&names)
+                        .map(|(s, name)| {
+                                let value = s.get(row).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+                                SqlValue::from_any(value, name)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                let row_bytes: usize = values.iter().map(SqlValue::byte_estimate).sum();
+                if !batch.is_empty() && (batch_bytes + row_bytes > byte_budget || batch.len() >= max_rows) {
+                        let rows = std::mem::take(&mut batch);
+                        let mut statement = insert_statement(table, &names, rows, &options.on_duplicate);
+                        affected += statement.build().execute(&mut *tx).await?.rows_affected();
+                        batch_bytes = 0;
+                }
+                batch_bytes += row_bytes;
+                batch.push(values);
+        }
+        let mut statement = insert_statement(table, &names, batch, &options.on_duplicate);
+        affected += statement.build().execute(&mut *tx).await?.rows_affected();
+        tx.commit().await?;
+
+        Ok(affected)
+}
@@ -0,0 +1,130 @@
+//! MySQL ENUM columns ~~> polars `Enum`
+//!
+//! Result metadata only says a column *is* an ENUM; its declared values come from
+//! `information_schema.COLUMNS.COLUMN_TYPE` (`enum('Sciences','Humanities','Other')`),
+//! matched to result columns by (case-insensitive) name.
+//! Categories keep their declared order, so sorting follows the declaration rather than the alphabet.
+//!
+//! When the values are known at compile time, `#[derive(SqlEnum)]` gives a typed Rust enum instead
+//! (`School`, `Grade`): it decodes straight from the column, rejecting anything undeclared.
+
+use polars::{export::arrow::array::Utf8ViewArray,
+             prelude::{DataType, create_enum_dtype}};
+use proc_macro::SqlEnum;
+use sqlx::MySqlPool;
+use tracing::{debug, instrument};
+
+/// `students.School`
+#[derive(SqlEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum School {
+        Sciences,
+        Humanities,
+        Other,
+}
+
+/// `enrollments.Grade`, in declared order (`Incomplete` last)
+#[derive(SqlEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Grade {
+        A,
+        B,
+        C,
+        D,
+        F,
+        Incomplete,
+}
+
+/// polars `Enum` with `values` as its categories, in order
+pub fn enum_dtype<S: AsRef<str>>(values: &[S]) -> DataType {
+        create_enum_dtype(Utf8ViewArray::from_slice_values(values))
+}
+
+/// `enum('a','b''c')` ~~> `["a", "b'c"]`; anything else (`set(...)`, `varchar(10)`) is `None`
+pub fn parse_enum_values(column_type: &str) -> Option<Vec<String>> {
+        let column_type = column_type.trim();
+        let head = column_type.get(..5)?;
+        if !head.eq_ignore_ascii_case("enum(") || !column_type.ends_with(')') {
+                return None;
+        }
+        let mut chars = column_type[5..column_type.len() - 1].chars().peekable();
+        let mut values = Vec::new();
+        loop {
+                match chars.next() {
+                        None => return Some(values),
+                        Some(',' | ' ') => continue,
+                        Some('\'') => {}
+                        Some(_) => return None,
+                }
+                let mut value = String::new();
+                loop {
+                        match chars.next()? {
+                                // '' is an escaped quote, a lone ' ends the value
+                                '\'' if chars.peek() == Some(&'\'') => {
+                                        chars.next();
+                                        value.push('\'');
+                                }
+                                '\'' => break,
+                                c => value.push(c),
+                        }
+                }
+                values.push(value);
+        }
+}
+
+/// One ENUM column as declared in the schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredEnum {
+        pub table:  String,
+        pub column: String,
+        pub values: Vec<String>,
+}
+
+/// ENUM columns of the current database
+#[derive(Debug, Clone, Default)]
+pub struct DeclaredEnums {
+        columns: Vec<DeclaredEnum>,
+}
+impl DeclaredEnums {
+        /// ENUM columns of `tables` (all tables if empty) in the connection's current database
+        #[instrument(name = "declared_enums", level = "debug", skip(pool))]
+        pub async fn load(pool: &MySqlPool, tables: &[String]) -> Result<Self, sqlx::Error> {
+                let rows: Vec<(String, String, String)> = sqlx::query_as(
+                        "SELECT TABLE_NAME, COLUMN_NAME, COLUMN_TYPE FROM information_schema.COLUMNS
+                         WHERE TABLE_SCHEMA = DATABASE() AND DATA_TYPE = 'enum'
+                         ORDER BY TABLE_NAME, ORDINAL_POSITION",
+                )
+                .fetch_all(pool)
+                .await?;
+                let columns = rows
+                        .into_iter()
+                        .filter(|(table, ..)| tables.is_empty() || tables.iter().any(|t| t.eq_ignore_ascii_case(table)))
+                        .filter_map(|(table, column, column_type)| {
+                                let values = parse_enum_values(&column_type)?;
+                                Some(DeclaredEnum { table, column, values })
+                        })
+                        .collect();
+                Ok(Self { columns })
+        }
+
+        pub fn columns(&self) -> &[DeclaredEnum] {
+                &self.columns
+        }
+
+        /// Declared values of the ENUM column called `name`
+        ///
+        /// `None` if no table has one, or if tables disagree on its values (e.g. `a.status` vs `b.status`):
+        /// a result column only carries its (possibly aliased) name, so there's no telling them apart.
+        pub fn values(&self, name: &str) -> Option<&[String]> {
+                let mut matches = self.columns.iter().filter(|e| e.column.eq_ignore_ascii_case(name));
+                let first = matches.next()?;
+                if matches.any(|other| other.values != first.values) {
+                        debug!(column = name, "ENUM column declared differently in several tables, kept as text");
+                        return None;
+                }
+                Some(&first.values)
+        }
+
+        /// polars `Enum` of the ENUM column called `name` (see `values`)
+        pub fn dtype(&self, name: &str) -> Option<DataType> {
+                self.values(name).map(enum_dtype)
+        }
+}
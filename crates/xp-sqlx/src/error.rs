@@ -0,0 +1,57 @@
+//! Crate-level error: every failure the query ~~> DataFrame path can hit, with enough context to report it
+//!
+//! `sqlx::Error` converts to `Query` (most of what goes wrong is running a statement);
+//! connection & configuration failures are mapped to `Connection` explicitly.
+
+use std::{fmt, path::PathBuf};
+
+use derive_more::{Display, From};
+use polars::prelude::PolarsError;
+
+use crate::{query_catalog::CatalogError, query_file::QueryFileError};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Display, derive_more::Error, From)]
+pub enum Error {
+        /// configuring or opening the pool
+        #[display("connecting to the database: {_0}")]
+        #[from(ignore)]
+        Connection(sqlx::Error),
+        /// preparing, binding or running a statement
+        #[display("query failed: {_0}")]
+        Query(sqlx::Error),
+        /// a value that wouldn't decode as its column's type
+        #[display("decoding column `{column}` ({type_name}): {source}")]
+        Decode { column: String, type_name: String, source: sqlx::Error },
+        /// building, stacking or writing a DataFrame
+        #[display("DataFrame: {_0}")]
+        Polars(PolarsError),
+        #[display("{}: {source}", path.display())]
+        #[from(ignore)]
+        Io { path: PathBuf, source: std::io::Error },
+        #[display("query file {_0}")]
+        QueryFile(QueryFileError),
+        #[display("query catalog: {_0}")]
+        Catalog(CatalogError),
+        /// serializing output (e.g. `--json`)
+        #[display("JSON: {_0}")]
+        Json(serde_json::Error),
+        /// installing the tracing subscriber (`telemetry`), e.g. when one is already set
+        #[display("telemetry: {_0}")]
+        Telemetry(tracing_subscriber::util::TryInitError),
+        /// terminal prompt failed (e.g. not a TTY)
+        #[display("prompt: {_0}")]
+        Prompt(dialoguer::Error),
+        /// a bad value or name given on the command line / at a prompt
+        #[display("{_0}")]
+        #[from(ignore)]
+        Input(#[error(not(source))] String),
+}
+
+/// Same as `Display`: `main() -> Result<(), Error>` prints this, and the message already carries its context
+impl fmt::Debug for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+        }
+}
@@ -0,0 +1,98 @@
+//! Writing a polars::DataFrame to disk
+//!
+//! `OutputArgs` is meant to be `#[command(flatten)]`ed into a bin's clap `Args`,
+//! giving it `--output <path>` and `--format <fmt>` (format inferred from the extension if not given).
+
+use std::{fs::File,
+          path::{Path, PathBuf}};
+
+use clap::{Args, ValueEnum};
+use polars::prelude::*;
+
+/// File formats a DataFrame can be written as
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+        Parquet,
+        Csv,
+        /// Arrow IPC (a.k.a. Feather v2)
+        Ipc,
+        /// newline delimited JSON, one object per row
+        Ndjson,
+        /// a single JSON array of row objects
+        Json,
+}
+impl ExportFormat {
+        /// Format from a file extension (`.parquet`/`.pq`, `.csv`, `.ipc`/`.arrow`/`.feather`, `.ndjson`/`.jsonl`,
+        /// `.json`)
+        pub fn from_path(path: &Path) -> Option<Self> {
+                let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+                Some(match ext.as_str() {
+                        "parquet" | "pq" => Self::Parquet,
+                        "csv" => Self::Csv,
+                        "ipc" | "arrow" | "feather" => Self::Ipc,
+                        "ndjson" | "jsonl" => Self::Ndjson,
+                        "json" => Self::Json,
+                        _ => return None,
+                })
+        }
+}
+
+/// `--output` / `--format` for bins that produce a DataFrame
+#[derive(Args, Debug, Clone, Default)]
+pub struct OutputArgs {
+        /// write the resulting DataFrame to this file
+        #[arg(short, long, global = true)]
+        pub output: Option<PathBuf>,
+        /// file format; inferred from the `--output` extension when omitted
+        #[arg(long, value_enum, requires = "output", global = true)]
+        pub format: Option<ExportFormat>,
+}
+impl OutputArgs {
+        /// Writes `df` if `--output` was given; returns the path written to
+        pub fn write(&self, df: &mut DataFrame) -> PolarsResult<Option<&Path>> {
+                let Some(path) = &self.output else { return Ok(None) };
+                write_dataframe_file(df, path, self.format)?;
+                Ok(Some(path))
+        }
+
+        /// Same format, `--output` with `_<name>` added to the file stem (`out.csv` ~~> `out_students.csv`),
+        /// for bins that write several frames
+        pub fn named(&self, name: &str) -> Self {
+                let output = self.output.as_ref().map(|path| {
+                        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                        let file = match path.extension() {
+                                Some(ext) => format!("{stem}_{name}.{}", ext.to_string_lossy()),
+                                None => format!("{stem}_{name}"),
+                        };
+                        path.with_file_name(file)
+                });
+                Self { output, format: self.format }
+        }
+}
+
+/// Polars::DataFrame ~~> file at `path`
+///
+/// `format: None` infers it from the extension; an unknown extension is an error rather than a guess.
+pub fn write_dataframe_file(df: &mut DataFrame, path: &Path, format: Option<ExportFormat>) -> PolarsResult<()> {
+        let format = format.or_else(|| ExportFormat::from_path(path)).ok_or_else(|| {
+                PolarsError::InvalidOperation(
+                        format!(
+                                "can't infer an output format from `{}`; pass --format (parquet, csv, ipc, ndjson, \
+                                 json)",
+                                path.display()
+                        )
+                        .into(),
+                )
+        })?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        match format {
+                ExportFormat::Parquet => ParquetWriter::new(file).finish(df).map(|_| ()),
+                ExportFormat::Csv => CsvWriter::new(file).include_header(true).finish(df),
+                ExportFormat::Ipc => IpcWriter::new(file).finish(df),
+                ExportFormat::Ndjson => JsonWriter::new(file).with_json_format(JsonFormat::JsonLines).finish(df),
+                ExportFormat::Json => JsonWriter::new(file).with_json_format(JsonFormat::Json).finish(df),
+        }
+}
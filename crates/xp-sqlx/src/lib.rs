@@ -1 +1,14 @@
+pub mod bench;
+pub mod connection;
+pub mod ddl;
+pub mod df_to_mysql;
+pub mod enums;
+pub mod error;
+pub mod export;
+pub mod models;
+pub mod params;
+pub mod query_catalog;
+pub mod query_file;
+pub mod schema;
 pub mod stream_to_df;
+pub mod telemetry;
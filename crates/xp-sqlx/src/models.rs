@@ -0,0 +1,312 @@
+//! Row structs for the `university` tables
+//!
+//! Generated by `cargo run -p xtask -- gen-models`; regenerate rather than edit.
+//! A `<Row>` per table (`FromRow`, `ToDataFrame`, `Display`) and `VecOf<Row>`, its columns.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use polars::prelude::{DataFrame, NamedFrom, PolarsResult, Series};
+use proc_macro::ToDataFrame;
+use sqlx::FromRow;
+
+use crate::enums::{Grade, School};
+
+/// Row of `courses`
+#[derive(Debug, Clone, PartialEq, FromRow, ToDataFrame)]
+pub struct Course {
+        #[sqlx(rename = "CourseID")]
+        #[df(rename = "CourseID")]
+        pub course_id:   i32,
+        #[sqlx(rename = "CourseName")]
+        #[df(rename = "CourseName")]
+        pub course_name: String,
+        #[sqlx(rename = "CourseCode")]
+        #[df(rename = "CourseCode")]
+        pub course_code: String,
+        #[sqlx(rename = "Credits")]
+        #[df(rename = "Credits")]
+        pub credits:     i32,
+}
+impl fmt::Display for Course {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "courses(CourseID: {}", self.course_id)?;
+                write!(f, ", CourseName: {}", self.course_name)?;
+                write!(f, ", CourseCode: {}", self.course_code)?;
+                write!(f, ", Credits: {}", self.credits)?;
+                f.write_str(")")
+        }
+}
+
+/// `Course`s, a Vec per column
+#[derive(Debug, Default)]
+pub struct VecOfCourse {
+        pub course_id:   Vec<i32>,
+        pub course_name: Vec<String>,
+        pub course_code: Vec<String>,
+        pub credits:     Vec<i32>,
+}
+impl VecOfCourse {
+        pub fn push(&mut self, row: Course) {
+                self.course_id.push(row.course_id);
+                self.course_name.push(row.course_name);
+                self.course_code.push(row.course_code);
+                self.credits.push(row.credits);
+        }
+
+        /// One column per field, named as in the table
+        pub fn into_dataframe(self) -> PolarsResult<DataFrame> {
+                DataFrame::new(vec![
+                        Series::new("CourseID".into(), self.course_id).into(),
+                        Series::new("CourseName".into(), self.course_name).into(),
+                        Series::new("CourseCode".into(), self.course_code).into(),
+                        Series::new("Credits".into(), self.credits).into(),
+                ])
+        }
+}
+impl Extend<Course> for VecOfCourse {
+        fn extend<I: IntoIterator<Item = Course>>(&mut self, rows: I) {
+                rows.into_iter().for_each(|row| self.push(row));
+        }
+}
+impl FromIterator<Course> for VecOfCourse {
+        fn from_iter<I: IntoIterator<Item = Course>>(rows: I) -> Self {
+                let mut columns = Self::default();
+                columns.extend(rows);
+                columns
+        }
+}
+
+/// Row of `enrollments`
+#[derive(Debug, Clone, PartialEq, FromRow, ToDataFrame)]
+pub struct Enrollment {
+        #[sqlx(rename = "EnrollmentID")]
+        #[df(rename = "EnrollmentID")]
+        pub enrollment_id:   i32,
+        #[sqlx(rename = "StudentID")]
+        #[df(rename = "StudentID")]
+        pub student_id:      i32,
+        #[sqlx(rename = "CourseID")]
+        #[df(rename = "CourseID")]
+        pub course_id:       i32,
+        #[sqlx(rename = "EnrollmentDate")]
+        #[df(rename = "EnrollmentDate")]
+        pub enrollment_date: Option<NaiveDate>,
+        #[sqlx(rename = "Grade")]
+        #[df(rename = "Grade", sql_enum)]
+        pub grade:           Option<Grade>,
+}
+impl fmt::Display for Enrollment {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "enrollments(EnrollmentID: {}", self.enrollment_id)?;
+                write!(f, ", StudentID: {}", self.student_id)?;
+                write!(f, ", CourseID: {}", self.course_id)?;
+                write!(f, ", EnrollmentDate: {}", OrNull(&self.enrollment_date))?;
+                write!(f, ", Grade: {}", OrNull(&self.grade))?;
+                f.write_str(")")
+        }
+}
+
+/// `Enrollment`s, a Vec per column
+#[derive(Debug, Default)]
+pub struct VecOfEnrollment {
+        pub enrollment_id:   Vec<i32>,
+        pub student_id:      Vec<i32>,
+        pub course_id:       Vec<i32>,
+        pub enrollment_date: Vec<Option<NaiveDate>>,
+        pub grade:           Vec<Option<Grade>>,
+}
+impl VecOfEnrollment {
+        pub fn push(&mut self, row: Enrollment) {
+                self.enrollment_id.push(row.enrollment_id);
+                self.student_id.push(row.student_id);
+                self.course_id.push(row.course_id);
+                self.enrollment_date.push(row.enrollment_date);
+                self.grade.push(row.grade);
+        }
+
+        /// One column per field, named as in the table
+        pub fn into_dataframe(self) -> PolarsResult<DataFrame> {
+                DataFrame::new(vec![
+                        Series::new("EnrollmentID".into(), self.enrollment_id).into(),
+                        Series::new("StudentID".into(), self.student_id).into(),
+                        Series::new("CourseID".into(), self.course_id).into(),
+                        Series::new("EnrollmentDate".into(), self.enrollment_date).into(),
+                        Grade::to_series("Grade".into(), self.grade)?.into(),
+                ])
+        }
+}
+impl Extend<Enrollment> for VecOfEnrollment {
+        fn extend<I: IntoIterator<Item = Enrollment>>(&mut self, rows: I) {
+                rows.into_iter().for_each(|row| self.push(row));
+        }
+}
+impl FromIterator<Enrollment> for VecOfEnrollment {
+        fn from_iter<I: IntoIterator<Item = Enrollment>>(rows: I) -> Self {
+                let mut columns = Self::default();
+                columns.extend(rows);
+                columns
+        }
+}
+
+/// Row of `professors`
+#[derive(Debug, Clone, PartialEq, FromRow, ToDataFrame)]
+pub struct Professor {
+        #[sqlx(rename = "ProfessorID")]
+        #[df(rename = "ProfessorID")]
+        pub professor_id: i32,
+        #[sqlx(rename = "FirstName")]
+        #[df(rename = "FirstName")]
+        pub first_name:   String,
+        #[sqlx(rename = "LastName")]
+        #[df(rename = "LastName")]
+        pub last_name:    String,
+        #[sqlx(rename = "Department")]
+        #[df(rename = "Department")]
+        pub department:   Option<String>,
+        #[sqlx(rename = "Email")]
+        #[df(rename = "Email")]
+        pub email:        Option<String>,
+}
+impl fmt::Display for Professor {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "professors(ProfessorID: {}", self.professor_id)?;
+                write!(f, ", FirstName: {}", self.first_name)?;
+                write!(f, ", LastName: {}", self.last_name)?;
+                write!(f, ", Department: {}", OrNull(&self.department))?;
+                write!(f, ", Email: {}", OrNull(&self.email))?;
+                f.write_str(")")
+        }
+}
+
+/// `Professor`s, a Vec per column
+#[derive(Debug, Default)]
+pub struct VecOfProfessor {
+        pub professor_id: Vec<i32>,
+        pub first_name:   Vec<String>,
+        pub last_name:    Vec<String>,
+        pub department:   Vec<Option<String>>,
+        pub email:        Vec<Option<String>>,
+}
+impl VecOfProfessor {
+        pub fn push(&mut self, row: Professor) {
+                self.professor_id.push(row.professor_id);
+                self.first_name.push(row.first_name);
+                self.last_name.push(row.last_name);
+                self.department.push(row.department);
+                self.email.push(row.email);
+        }
+
+        /// One column per field, named as in the table
+        pub fn into_dataframe(self) -> PolarsResult<DataFrame> {
+                DataFrame::new(vec![
+                        Series::new("ProfessorID".into(), self.professor_id).into(),
+                        Series::new("FirstName".into(), self.first_name).into(),
+                        Series::new("LastName".into(), self.last_name).into(),
+                        Series::new("Department".into(), self.department).into(),
+                        Series::new("Email".into(), self.email).into(),
+                ])
+        }
+}
+impl Extend<Professor> for VecOfProfessor {
+        fn extend<I: IntoIterator<Item = Professor>>(&mut self, rows: I) {
+                rows.into_iter().for_each(|row| self.push(row));
+        }
+}
+impl FromIterator<Professor> for VecOfProfessor {
+        fn from_iter<I: IntoIterator<Item = Professor>>(rows: I) -> Self {
+                let mut columns = Self::default();
+                columns.extend(rows);
+                columns
+        }
+}
+
+/// Row of `students`
+#[derive(Debug, Clone, PartialEq, FromRow, ToDataFrame)]
+pub struct Student {
+        #[sqlx(rename = "StudentID")]
+        #[df(rename = "StudentID")]
+        pub student_id:    i32,
+        #[sqlx(rename = "FirstName")]
+        #[df(rename = "FirstName")]
+        pub first_name:    String,
+        #[sqlx(rename = "LastName")]
+        #[df(rename = "LastName")]
+        pub last_name:     String,
+        #[sqlx(rename = "DateOfBirth")]
+        #[df(rename = "DateOfBirth")]
+        pub date_of_birth: Option<NaiveDate>,
+        #[sqlx(rename = "School")]
+        #[df(rename = "School", sql_enum)]
+        pub school:        Option<School>,
+        #[sqlx(rename = "Email")]
+        #[df(rename = "Email")]
+        pub email:         Option<String>,
+}
+impl fmt::Display for Student {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "students(StudentID: {}", self.student_id)?;
+                write!(f, ", FirstName: {}", self.first_name)?;
+                write!(f, ", LastName: {}", self.last_name)?;
+                write!(f, ", DateOfBirth: {}", OrNull(&self.date_of_birth))?;
+                write!(f, ", School: {}", OrNull(&self.school))?;
+                write!(f, ", Email: {}", OrNull(&self.email))?;
+                f.write_str(")")
+        }
+}
+
+/// `Student`s, a Vec per column
+#[derive(Debug, Default)]
+pub struct VecOfStudent {
+        pub student_id:    Vec<i32>,
+        pub first_name:    Vec<String>,
+        pub last_name:     Vec<String>,
+        pub date_of_birth: Vec<Option<NaiveDate>>,
+        pub school:        Vec<Option<School>>,
+        pub email:         Vec<Option<String>>,
+}
+impl VecOfStudent {
+        pub fn push(&mut self, row: Student) {
+                self.student_id.push(row.student_id);
+                self.first_name.push(row.first_name);
+                self.last_name.push(row.last_name);
+                self.date_of_birth.push(row.date_of_birth);
+                self.school.push(row.school);
+                self.email.push(row.email);
+        }
+
+        /// One column per field, named as in the table
+        pub fn into_dataframe(self) -> PolarsResult<DataFrame> {
+                DataFrame::new(vec![
+                        Series::new("StudentID".into(), self.student_id).into(),
+                        Series::new("FirstName".into(), self.first_name).into(),
+                        Series::new("LastName".into(), self.last_name).into(),
+                        Series::new("DateOfBirth".into(), self.date_of_birth).into(),
+                        School::to_series("School".into(), self.school)?.into(),
+                        Series::new("Email".into(), self.email).into(),
+                ])
+        }
+}
+impl Extend<Student> for VecOfStudent {
+        fn extend<I: IntoIterator<Item = Student>>(&mut self, rows: I) {
+                rows.into_iter().for_each(|row| self.push(row));
+        }
+}
+impl FromIterator<Student> for VecOfStudent {
+        fn from_iter<I: IntoIterator<Item = Student>>(rows: I) -> Self {
+                let mut columns = Self::default();
+                columns.extend(rows);
+                columns
+        }
+}
+
+/// An `Option` field shown as its value, or `NULL`
+struct OrNull<'a, T>(&'a Option<T>);
+impl<T: fmt::Display> fmt::Display for OrNull<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.0 {
+                        Some(value) => value.fmt(f),
+                        None => f.write_str("NULL"),
+                }
+        }
+}
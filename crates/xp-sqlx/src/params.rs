@@ -0,0 +1,600 @@
+//! Typed `?` parameters for ad hoc (runtime) MySQL, SQLite & Postgres queries
+//!
+//! Postgres reports each parameter's type, but neither MySQL's nor SQLite's prepare does: only *how many* there
+//! are (sqlx reports `Either::Right(count)`; MySQL's wire types are all `VAR_STRING`).
+//! So the type of each `?` is inferred from the column it is compared against
+//! (`StudentID = ?`, `DateOfBirth BETWEEN ? AND ?`, `School IN (?, ?)`, `LIMIT ?`),
+//! looking the column up in the tables the query reads from.
+//! Anything we can't place (e.g. `CONCAT('%', ?, '%')`) is treated as text.
+//!
+//! Query files are written with `?`; for Postgres they become `$1::<type>, $2::<type>, ...`
+//! (see `ParamArguments::placeholder_sql`), leaving the jsonb operators `?`, `?|` & `?&` alone.
+//! A text parameter compared with a non-text column (uuid, an enum type, ...) needs a cast in the query itself:
+//! `WHERE id = ?::uuid`.
+
+use std::borrow::Cow;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use derive_more::Display;
+use sqlx::{Arguments, Column, Database, Encode, Executor, Pool, Statement, Type, TypeInfo, error::BoxDynError,
+           mysql::MySqlArguments, postgres::PgArguments, sqlite::SqliteArguments};
+
+/// What a parameter is parsed & bound as
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+        #[display("integer")]
+        Int,
+        #[display("unsigned integer")]
+        UInt,
+        #[display("float")]
+        Float,
+        #[display("decimal")]
+        Decimal,
+        #[display("boolean")]
+        Bool,
+        #[display("date (YYYY-MM-DD)")]
+        Date,
+        #[display("datetime (YYYY-MM-DD HH:MM:SS)")]
+        Datetime,
+        #[display("time (HH:MM:SS)")]
+        Time,
+        #[display("text")]
+        Text,
+}
+impl ParamKind {
+        /// Type name (as in `TypeInfo::name()` or a column definition, e.g. `INT`, `VARCHAR(100)`, `INTEGER`)
+        ///
+        /// Keep in step with `stream_to_df::mysql_column_kind` (result columns)
+        /// and `sql_queries!`'s `rust_type` in `proc_macro` (typed arguments of the generated functions).
+        pub fn from_type_name(name: &str) -> Self {
+                let upper = name.trim().to_ascii_uppercase();
+                let base = upper.split(['(', ' ']).next().unwrap_or_default();
+                let unsigned = upper.contains("UNSIGNED");
+                match base {
+                        "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" | "BIGINT" | "YEAR" if unsigned => {
+                                Self::UInt
+                        }
+                        "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" | "BIGINT" | "YEAR" | "INT2"
+                        | "INT4" | "INT8" => Self::Int,
+                        "FLOAT" | "DOUBLE" | "REAL" | "FLOAT4" | "FLOAT8" => Self::Float,
+                        "DECIMAL" | "NUMERIC" => Self::Decimal,
+                        "BOOLEAN" | "BOOL" => Self::Bool,
+                        "DATE" => Self::Date,
+                        "DATETIME" | "TIMESTAMP" | "TIMESTAMPTZ" => Self::Datetime,
+                        "TIME" => Self::Time,
+                        // VARCHAR, CHAR, TEXT, ENUM, SET, JSON, ...
+                        _ => Self::Text,
+                }
+        }
+
+        pub fn from_type_info(type_info: &impl TypeInfo) -> Self {
+                Self::from_type_name(type_info.name())
+        }
+
+        /// User input ~~> value, or a message saying what was expected
+        pub fn parse(self, input: &str) -> Result<ParamValue, String> {
+                let s = input.trim();
+                let bad = |e: &dyn std::fmt::Display| format!("`{s}` is not a valid {self}: {e}");
+                Ok(match self {
+                        Self::Int => ParamValue::Int(s.parse().map_err(|e| bad(&e))?),
+                        Self::UInt => ParamValue::UInt(s.parse().map_err(|e| bad(&e))?),
+                        Self::Float => ParamValue::Float(s.parse().map_err(|e| bad(&e))?),
+                        Self::Decimal => {
+                                let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+                                let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+                                let all_digits = |p: &str| p.bytes().all(|b| b.is_ascii_digit());
+                                if (int.is_empty() && frac.is_empty()) || !all_digits(int) || !all_digits(frac) {
+                                        return Err(bad(&"expected digits with an optional `.` fraction"));
+                                }
+                                ParamValue::Decimal(s.to_string())
+                        }
+                        Self::Bool => ParamValue::Bool(match s.to_ascii_lowercase().as_str() {
+                                "true" | "t" | "yes" | "y" | "1" => true,
+                                "false" | "f" | "no" | "n" | "0" => false,
+                                _ => return Err(bad(&"expected true/false")),
+                        }),
+                        Self::Date => ParamValue::Date(NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| bad(&e))?),
+                        Self::Datetime => ParamValue::Datetime(
+                                NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+                                        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S"))
+                                        .map_err(|e| bad(&e))?,
+                        ),
+                        Self::Time => ParamValue::Time(NaiveTime::parse_from_str(s, "%H:%M:%S").map_err(|e| bad(&e))?),
+                        // text is taken verbatim
+                        Self::Text => ParamValue::Text(input.to_string()),
+                })
+        }
+}
+
+/// A parsed parameter, ready to bind
+#[derive(Debug, Display, Clone, PartialEq)]
+pub enum ParamValue {
+        Int(i64),
+        UInt(u64),
+        Float(f64),
+        /// validated decimal literal; sent as text so MySQL converts it exactly
+        Decimal(String),
+        Bool(bool),
+        Date(NaiveDate),
+        Datetime(NaiveDateTime),
+        Time(NaiveTime),
+        Text(String),
+}
+impl ParamValue {
+        pub fn add_to(self, arguments: &mut impl ParamArguments) -> Result<(), sqlx::Error> {
+                arguments.add_param(self).map_err(sqlx::Error::Encode)
+        }
+}
+
+/// Argument buffers a `ParamValue` can be bound into
+pub trait ParamArguments {
+        fn add_param(&mut self, value: ParamValue) -> Result<(), BoxDynError>;
+
+        /// `sql`, written with `?` placeholders, as this backend wants it.
+        /// `kinds` (once known) are what each parameter will be bound as.
+        fn placeholder_sql<'s>(sql: &'s str, _kinds: Option<&[ParamKind]>) -> Cow<'s, str> {
+                Cow::Borrowed(sql)
+        }
+}
+impl ParamArguments for MySqlArguments {
+        fn add_param(&mut self, value: ParamValue) -> Result<(), BoxDynError> {
+                match value {
+                        ParamValue::UInt(v) => self.add(v),
+                        value => add_signed(self, value),
+                }
+        }
+}
+impl ParamArguments for SqliteArguments<'_> {
+        /// SQLite integers are signed 64 bit
+        fn add_param(&mut self, value: ParamValue) -> Result<(), BoxDynError> {
+                match value {
+                        ParamValue::UInt(v) => self.add(i64::try_from(v)?),
+                        value => add_signed(self, value),
+                }
+        }
+}
+
+impl ParamArguments for PgArguments {
+        fn add_param(&mut self, value: ParamValue) -> Result<(), BoxDynError> {
+                add_signed(self, value)
+        }
+
+        /// `?` ~~> `$n`, cast to the type the value is bound as once `kinds` are known
+        /// (a prepared `$1` is typed after what it's compared with, e.g. INT4, and would reject an INT8).
+        /// SQL already written with `$n` is left as is.
+        fn placeholder_sql<'s>(sql: &'s str, kinds: Option<&[ParamKind]>) -> Cow<'s, str> {
+                let mut n = 0;
+                replace_placeholders(sql, || {
+                        n += 1;
+                        match kinds.and_then(|k| k.get(n - 1)) {
+                                Some(kind) => format!("${n}::{}", kind.postgres_type()),
+                                None => format!("${n}"),
+                        }
+                })
+        }
+}
+
+impl ParamKind {
+        /// Postgres type a value of this kind is bound as
+        fn postgres_type(self) -> &'static str {
+                match self {
+                        Self::Int | Self::UInt => "int8",
+                        Self::Float => "float8",
+                        // sent as text (see `ParamValue::Decimal`)
+                        Self::Decimal => "text::numeric",
+                        Self::Bool => "bool",
+                        Self::Date => "date",
+                        Self::Datetime => "timestamp",
+                        Self::Time => "time",
+                        Self::Text => "text",
+                }
+        }
+}
+
+/// Words after which a `?` is a value, not the jsonb `?` operator
+const PG_VALUE_KEYWORDS: &[&str] = &[
+        "AND",
+        "ANY",
+        "ALL",
+        "BETWEEN",
+        "BY",
+        "CASE",
+        "DISTINCT",
+        "ELSE",
+        "FETCH",
+        "FROM",
+        "HAVING",
+        "IN",
+        "ILIKE",
+        "IS",
+        "LIKE",
+        "LIMIT",
+        "NOT",
+        "OFFSET",
+        "ON",
+        "OR",
+        "RETURNING",
+        "SELECT",
+        "SET",
+        "SIMILAR",
+        "THEN",
+        "TO",
+        "WHEN",
+        "WHERE",
+];
+
+/// Every `?` placeholder replaced by `next()`, lexing as Postgres does:
+/// - skipped: '...' (backslashes are plain characters, except in E'...'), "...", $tag$...$tag$ & (nested) comments
+/// - a `?` right after an operand (`doc ? 'key'`) and `?|` / `?&` are jsonb operators
+/// - borrowed as is if the SQL already uses `$n` parameters
+fn replace_placeholders(sql: &str, mut next: impl FnMut() -> String) -> Cow<'_, str> {
+        let chars: Vec<char> = sql.chars().collect();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+        let mut out = String::with_capacity(sql.len());
+        // whether the last token ends an operand (a value, name or `)`), after which `?` is an operator
+        let mut after_operand = false;
+        let mut i = 0;
+        while i < chars.len() {
+                let c = chars[i];
+                let (end, operand) = match c {
+                        '?' if after_operand || matches!(chars.get(i + 1), Some('|' | '&')) => {
+                                (i + 1 + usize::from(matches!(chars.get(i + 1), Some('|' | '&'))), false)
+                        }
+                        '?' => {
+                                out.push_str(&next());
+                                i += 1;
+                                after_operand = true;
+                                continue;
+                        }
+                        _ if c.is_whitespace() => (i + 1, after_operand),
+                        '-' if chars.get(i + 1) == Some(&'-') => {
+                                let end = chars[i..]
+                                        .iter()
+                                        .position(|&n| n == '\n')
+                                        .map_or(chars.len(), |p| i + p);
+                                (end, after_operand)
+                        }
+                        '/' if chars.get(i + 1) == Some(&'*') => {
+                                let (mut j, mut depth) = (i + 2, 1);
+                                while j < chars.len() && depth > 0 {
+                                        match (chars[j], chars.get(j + 1)) {
+                                                ('/', Some('*')) => (j, depth) = (j + 2, depth + 1),
+                                                ('*', Some('/')) => (j, depth) = (j + 2, depth - 1),
+                                                _ => j += 1,
+                                        }
+                                }
+                                (j, after_operand)
+                        }
+                        '\'' | '"' => (quoted_end(&chars, i, false), true),
+                        '$' if chars.get(i + 1).is_some_and(char::is_ascii_digit) => return Cow::Borrowed(sql),
+                        '$' => match dollar_tag(&chars, i) {
+                                Some(tag) => {
+                                        let end = (i + tag.len()..=chars.len().saturating_sub(tag.len()))
+                                                .find(|&j| chars[j..].starts_with(&tag))
+                                                .map_or(chars.len(), |j| j + tag.len());
+                                        (end, true)
+                                }
+                                None => (i + 1, false),
+                        },
+                        _ if is_word(c) => {
+                                let end = chars[i..]
+                                        .iter()
+                                        .position(|&n| !is_word(n))
+                                        .map_or(chars.len(), |p| i + p);
+                                let word: String = chars[i..end].iter().collect();
+                                if word.eq_ignore_ascii_case("e") && chars.get(end) == Some(&'\'') {
+                                        (quoted_end(&chars, end, true), true)
+                                } else {
+                                        let keyword = PG_VALUE_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(&word));
+                                        (end, !keyword)
+                                }
+                        }
+                        ')' | ']' => (i + 1, true),
+                        _ => (i + 1, false),
+                };
+                let end = end.min(chars.len());
+                out.extend(&chars[i..end]);
+                after_operand = operand;
+                i = end;
+        }
+        Cow::Owned(out)
+}
+
+/// End (exclusive) of the '...' or "..." starting at `start`; a doubled quote is an escaped one,
+/// and with `backslash` (E'...') so is `\'`
+fn quoted_end(chars: &[char], start: usize, backslash: bool) -> usize {
+        let quote = chars[start];
+        let mut j = start + 1;
+        while j < chars.len() {
+                match chars[j] {
+                        '\\' if backslash => j += 2,
+                        c if c == quote && chars.get(j + 1) == Some(&quote) => j += 2,
+                        c if c == quote => return j + 1,
+                        _ => j += 1,
+                }
+        }
+        chars.len()
+}
+
+/// The `$tag$` (or `$$`) opening a dollar-quoted string at `start`, if there is one
+fn dollar_tag(chars: &[char], start: usize) -> Option<Vec<char>> {
+        let len = chars[start + 1..]
+                .iter()
+                .position(|&c| !(c.is_alphanumeric() || c == '_'))?;
+        let tag = &chars[start..start + len + 2];
+        (tag[len + 1] == '$').then(|| tag.to_vec())
+}
+
+/// Everything but `UInt`, which not every backend can encode
+fn add_signed<'q, A>(arguments: &mut A, value: ParamValue) -> Result<(), BoxDynError>
+where
+        A: Arguments<'q>,
+        i64: Encode<'q, A::Database> + Type<A::Database>,
+        f64: Encode<'q, A::Database> + Type<A::Database>,
+        bool: Encode<'q, A::Database> + Type<A::Database>,
+        String: Encode<'q, A::Database> + Type<A::Database>,
+        NaiveDate: Encode<'q, A::Database> + Type<A::Database>,
+        NaiveDateTime: Encode<'q, A::Database> + Type<A::Database>,
+        NaiveTime: Encode<'q, A::Database> + Type<A::Database>,
+{
+        match value {
+                ParamValue::Int(v) => arguments.add(v),
+                ParamValue::UInt(v) => arguments.add(i64::try_from(v)?),
+                ParamValue::Float(v) => arguments.add(v),
+                ParamValue::Decimal(v) | ParamValue::Text(v) => arguments.add(v),
+                ParamValue::Bool(v) => arguments.add(v),
+                ParamValue::Date(v) => arguments.add(v),
+                ParamValue::Datetime(v) => arguments.add(v),
+                ParamValue::Time(v) => arguments.add(v),
+        }
+}
+
+/// SQL token, just enough to find what each `?` is compared with
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+        /// bare or backticked identifier (or keyword); dotted names keep only the last part
+        Ident(String),
+        Placeholder,
+        /// operators & punctuation
+        Symbol(String),
+        /// string & numeric literals
+        Literal,
+}
+impl Token {
+        fn is_keyword(&self, keyword: &str) -> bool {
+                matches!(self, Self::Ident(i) if i.eq_ignore_ascii_case(keyword))
+        }
+
+        fn is_symbol(&self, symbol: &str) -> bool {
+                matches!(self, Self::Symbol(s) if s == symbol)
+        }
+
+        fn is_comparison(&self) -> bool {
+                match self {
+                        Self::Symbol(s) => matches!(s.as_str(), "=" | "<" | ">" | "<=" | ">=" | "<>" | "!=" | "<=>"),
+                        Self::Ident(_) => self.is_keyword("LIKE"),
+                        _ => false,
+                }
+        }
+}
+
+fn tokenize(sql: &str) -> Vec<Token> {
+        let chars: Vec<char> = sql.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+                let c = chars[i];
+                match c {
+                        _ if c.is_whitespace() => i += 1,
+                        '-' if chars.get(i + 1) == Some(&'-') => {
+                                while i < chars.len() && chars[i] != '\n' {
+                                        i += 1;
+                                }
+                        }
+                        '#' => {
+                                while i < chars.len() && chars[i] != '\n' {
+                                        i += 1;
+                                }
+                        }
+                        '/' if chars.get(i + 1) == Some(&'*') => {
+                                i += 2;
+                                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                                        i += 1;
+                                }
+                                i += 2;
+                        }
+                        '\'' | '"' => {
+                                i += 1;
+                                while i < chars.len() && chars[i] != c {
+                                        i += if chars[i] == '\\' { 2 } else { 1 };
+                                }
+                                i += 1;
+                                tokens.push(Token::Literal);
+                        }
+                        '`' => {
+                                let start = i + 1;
+                                i = start;
+                                while i < chars.len() && chars[i] != '`' {
+                                        i += 1;
+                                }
+                                push_ident(&mut tokens, chars[start..i.min(chars.len())].iter().collect());
+                                i += 1;
+                        }
+                        '?' => {
+                                tokens.push(Token::Placeholder);
+                                i += 1;
+                        }
+                        _ if c.is_ascii_digit() => {
+                                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                                        i += 1;
+                                }
+                                tokens.push(Token::Literal);
+                        }
+                        _ if c.is_alphabetic() || c == '_' || c == '$' => {
+                                let start = i;
+                                while i < chars.len()
+                                        && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                                {
+                                        i += 1;
+                                }
+                                push_ident(&mut tokens, chars[start..i].iter().collect());
+                        }
+                        '<' | '>' | '!' | '=' => {
+                                let start = i;
+                                while i < chars.len() && matches!(chars[i], '<' | '>' | '!' | '=') && i - start < 3 {
+                                        i += 1;
+                                }
+                                tokens.push(Token::Symbol(chars[start..i].iter().collect()));
+                        }
+                        _ => {
+                                tokens.push(Token::Symbol(c.to_string()));
+                                i += 1;
+                        }
+                }
+        }
+        tokens
+}
+
+/// `a`.`b` / a.b: replace the qualifier with the column part
+fn push_ident(tokens: &mut Vec<Token>, ident: String) {
+        if tokens.last().is_some_and(|t| t.is_symbol(".")) {
+                tokens.pop();
+                if matches!(tokens.last(), Some(Token::Ident(_))) {
+                        tokens.pop();
+                }
+        }
+        tokens.push(Token::Ident(ident));
+}
+
+/// Table names following `FROM` / `JOIN` (comma separated lists included)
+pub fn referenced_tables(sql: &str) -> Vec<String> {
+        let tokens = tokenize(sql);
+        let mut tables: Vec<String> = Vec::new();
+        for (i, token) in tokens.iter().enumerate() {
+                if !(token.is_keyword("FROM") || token.is_keyword("JOIN")) {
+                        continue;
+                }
+                let mut j = i + 1;
+                while let Some(Token::Ident(name)) = tokens.get(j) {
+                        if !tables.iter().any(|t| t.eq_ignore_ascii_case(name)) {
+                                tables.push(name.clone());
+                        }
+                        // skip an alias, then continue past a comma
+                        j += 1;
+                        if tokens.get(j).is_some_and(|t| t.is_keyword("AS")) {
+                                j += 1;
+                        }
+                        if matches!(tokens.get(j), Some(Token::Ident(a)) if !is_clause_keyword(a)) {
+                                j += 1;
+                        }
+                        if !tokens.get(j).is_some_and(|t| t.is_symbol(",")) {
+                                break;
+                        }
+                        j += 1;
+                }
+        }
+        tables
+}
+
+fn is_clause_keyword(ident: &str) -> bool {
+        const KEYWORDS: [&str; 14] = [
+                "WHERE", "JOIN", "INNER", "LEFT", "RIGHT", "CROSS", "ON", "USING", "GROUP", "ORDER", "LIMIT", "HAVING",
+                "UNION", "NATURAL",
+        ];
+        KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(ident))
+}
+
+/// Kind of each `?` in `sql`, in order, given the columns it may be compared against
+pub fn infer_param_kinds(sql: &str, columns: &[impl Column]) -> Vec<ParamKind> {
+        let tokens = tokenize(sql);
+        let kind_of = |name: &str| {
+                columns.iter()
+                        .find(|c| c.name().eq_ignore_ascii_case(name))
+                        .map(|c| ParamKind::from_type_info(c.type_info()))
+        };
+        let ident_at = |j: Option<usize>| match j.and_then(|j| tokens.get(j)) {
+                Some(Token::Ident(name)) => kind_of(name),
+                _ => None,
+        };
+
+        tokens.iter()
+                .enumerate()
+                .filter(|(_, t)| **t == Token::Placeholder)
+                .map(|(i, _)| {
+                        let prev = |n: usize| i.checked_sub(n).map(|j| &tokens[j]);
+                        // `col <op> ?` / `? <op> col`
+                        if prev(1).is_some_and(Token::is_comparison) {
+                                if let Some(kind) = ident_at(i.checked_sub(2)) {
+                                        return kind;
+                                }
+                        }
+                        if tokens.get(i + 1).is_some_and(Token::is_comparison) {
+                                if let Some(kind) = ident_at(Some(i + 2)) {
+                                        return kind;
+                                }
+                        }
+                        // `col BETWEEN ? AND ?`
+                        if prev(1).is_some_and(|t| t.is_keyword("BETWEEN")) {
+                                if let Some(kind) = ident_at(i.checked_sub(2)) {
+                                        return kind;
+                                }
+                        }
+                        if prev(1).is_some_and(|t| t.is_keyword("AND"))
+                                && prev(2) == Some(&Token::Placeholder)
+                                && prev(3).is_some_and(|t| t.is_keyword("BETWEEN"))
+                        {
+                                if let Some(kind) = ident_at(i.checked_sub(4)) {
+                                        return kind;
+                                }
+                        }
+                        // `col IN (?, ?, ...)`
+                        let mut j = i;
+                        while j > 0 && (tokens[j - 1] == Token::Placeholder || tokens[j - 1].is_symbol(",")) {
+                                j -= 1;
+                        }
+                        if j >= 2 && tokens[j - 1].is_symbol("(") && tokens[j - 2].is_keyword("IN") {
+                                if let Some(kind) = ident_at(j.checked_sub(3)) {
+                                        return kind;
+                                }
+                        }
+                        // `LIMIT ?`, `LIMIT ?, ?`, `OFFSET ?`
+                        if prev(1).is_some_and(|t| t.is_keyword("LIMIT") || t.is_keyword("OFFSET"))
+                                || (prev(1).is_some_and(|t| t.is_symbol(","))
+                                        && prev(2) == Some(&Token::Placeholder)
+                                        && prev(3).is_some_and(|t| t.is_keyword("LIMIT")))
+                        {
+                                return ParamKind::UInt;
+                        }
+                        ParamKind::Text
+                })
+                .collect()
+}
+
+/// Kind of each parameter of `sql`, looking compared columns up in the tables it reads from
+///
+/// `statement_columns` (the prepared statement's output) are consulted first, then the tables' own columns.
+/// The result always has `param_count` entries (missing ones are `Text`).
+pub async fn param_kinds<DB>(
+        pool: &Pool<DB>,
+        sql: &str,
+        statement_columns: &[DB::Column],
+        param_count: usize,
+) -> Result<Vec<ParamKind>, sqlx::Error>
+where
+        DB: Database,
+        DB::Column: Clone,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+        let mut columns = statement_columns.to_vec();
+        for table in referenced_tables(sql) {
+                let describe = format!("SELECT * FROM `{}` LIMIT 0", table.replace('`', "``"));
+                // a CTE or derived table name isn't a real table; just skip it
+                let prepared = pool.prepare(describe.as_str()).await;
+                if let Ok(statement) = &prepared {
+                        columns.extend(statement.columns().iter().cloned());
+                }
+        }
+        let mut kinds = infer_param_kinds(sql, &columns);
+        kinds.resize(param_count, ParamKind::Text);
+        Ok(kinds)
+}
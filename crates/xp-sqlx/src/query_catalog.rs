@@ -0,0 +1,185 @@
+//! All the query files we know about: embedded at compile time & found at runtime
+//!
+//! Sources are merged in order, later ones winning a name collision (each collision is recorded):
+//! 1. the embedded `include_dir!` directory
+//! 2. `$XDG_CONFIG_HOME/xp-sqlx/queries` (or the platform equivalent, via `directories`)
+//! 3. `XP_SQLX_QUERIES_DIR` (a path list, like `PATH`)
+//! 4. `--queries-dir` flags
+//!
+//! Subfolders are namespaces: `reports/monthly.sql` is the query `reports/monthly`
+//! (a `-- name:` header replaces only the last part).
+
+use std::{fmt,
+          path::{Path, PathBuf}};
+
+use derive_more::{Display, Error, From};
+use directories::ProjectDirs;
+use include_dir::Dir;
+
+use crate::query_file::{QueryFile, QueryFileError};
+
+/// env var holding extra query directories
+pub const QUERIES_DIR_ENV: &str = "XP_SQLX_QUERIES_DIR";
+
+/// Where a catalog query came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuerySource {
+        /// compiled in; path relative to the embedded directory
+        Embedded(PathBuf),
+        /// read from disk at runtime
+        File(PathBuf),
+}
+impl fmt::Display for QuerySource {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                        Self::Embedded(path) => write!(f, "<embedded>/{}", path.display()),
+                        Self::File(path) => write!(f, "{}", path.display()),
+                }
+        }
+}
+
+/// A query and where it was loaded from
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogEntry {
+        pub query:  QueryFile,
+        pub source: QuerySource,
+}
+
+/// Two sources defined the same query name; `kept` won
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[display("query `{name}` from {kept} shadows {shadowed}")]
+pub struct Conflict {
+        pub name:     String,
+        pub kept:     QuerySource,
+        pub shadowed: QuerySource,
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum CatalogError {
+        #[display("reading {}: {source}", path.display())]
+        #[from(ignore)]
+        Io {
+                path:   PathBuf,
+                source: std::io::Error,
+        },
+        Parse(QueryFileError),
+}
+
+/// Merged, namespaced set of query files
+#[derive(Debug, Clone, Default)]
+pub struct QueryCatalog {
+        entries:   Vec<CatalogEntry>,
+        conflicts: Vec<Conflict>,
+}
+impl QueryCatalog {
+        /// Embedded queries + the standard runtime directories (see module docs), `cli_dirs` last
+        pub fn load(embedded: &Dir<'_>, cli_dirs: &[PathBuf]) -> Result<Self, CatalogError> {
+                let mut catalog = Self::default();
+                catalog.add_embedded(embedded)?;
+                for dir in Self::runtime_dirs(cli_dirs) {
+                        catalog.add_dir(&dir)?;
+                }
+                Ok(catalog)
+        }
+
+        /// Runtime directories in merge order; the XDG & env ones only if they exist
+        pub fn runtime_dirs(cli_dirs: &[PathBuf]) -> Vec<PathBuf> {
+                let xdg = ProjectDirs::from("", "", "xp-sqlx").map(|p| p.config_dir().join("queries"));
+                let env = std::env::var_os(QUERIES_DIR_ENV)
+                        .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+                        .unwrap_or_default();
+                xdg.into_iter()
+                        .chain(env)
+                        .filter(|dir| dir.is_dir())
+                        .chain(cli_dirs.iter().cloned())
+                        .collect()
+        }
+
+        /// Every `.sql` file in `dir`, recursively
+        pub fn add_embedded(&mut self, dir: &Dir<'_>) -> Result<(), CatalogError> {
+                for file in dir.files().filter(|f| is_sql(f.path())) {
+                        let sql = file.contents_utf8().ok_or_else(|| CatalogError::Io {
+                                path:   file.path().to_path_buf(),
+                                source: std::io::Error::new(std::io::ErrorKind::InvalidData, "not valid UTF-8"),
+                        })?;
+                        self.insert(file.path(), sql, QuerySource::Embedded(file.path().to_path_buf()))?;
+                }
+                for sub in dir.dirs() {
+                        self.add_embedded(sub)?;
+                }
+                Ok(())
+        }
+
+        /// Every `.sql` file under `root`, recursively
+        pub fn add_dir(&mut self, root: &Path) -> Result<(), CatalogError> {
+                let mut pending = vec![root.to_path_buf()];
+                while let Some(dir) = pending.pop() {
+                        let io_error = |source| CatalogError::Io { path: dir.clone(), source };
+                        let mut paths = std::fs::read_dir(&dir)
+                                .map_err(io_error)?
+                                .map(|entry| entry.map(|e| e.path()))
+                                .collect::<Result<Vec<_>, _>>()
+                                .map_err(io_error)?;
+                        // deterministic order (read_dir's isn't)
+                        paths.sort();
+                        for path in paths {
+                                if path.is_dir() {
+                                        pending.push(path);
+                                } else if is_sql(&path) {
+                                        let sql = std::fs::read_to_string(&path)
+                                                .map_err(|source| CatalogError::Io { path: path.clone(), source })?;
+                                        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+                                        self.insert(&relative, &sql, QuerySource::File(path))?;
+                                }
+                        }
+                }
+                Ok(())
+        }
+
+        /// Parses & namespaces one file, replacing (and recording) any same-named query
+        fn insert(&mut self, relative: &Path, sql: &str, source: QuerySource) -> Result<(), QueryFileError> {
+                let mut query = QueryFile::parse(relative, sql)?;
+                let namespace: Vec<_> = relative
+                        .parent()
+                        .into_iter()
+                        .flat_map(|p| p.components())
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect();
+                if !namespace.is_empty() {
+                        query.name = format!("{}/{}", namespace.join("/"), query.name);
+                }
+                let entry = CatalogEntry { query, source };
+                match self.entries.iter_mut().find(|e| e.query.name == entry.query.name) {
+                        Some(existing) => {
+                                self.conflicts.push(Conflict {
+                                        name:     entry.query.name.clone(),
+                                        kept:     entry.source.clone(),
+                                        shadowed: existing.source.clone(),
+                                });
+                                *existing = entry;
+                        }
+                        None => self.entries.push(entry),
+                }
+                Ok(())
+        }
+
+        pub fn get(&self, name: &str) -> Option<&CatalogEntry> {
+                self.entries.iter().find(|e| e.query.name == name)
+        }
+
+        /// Queries sorted by name
+        pub fn entries(&self) -> Vec<&CatalogEntry> {
+                let mut entries: Vec<_> = self.entries.iter().collect();
+                entries.sort_by(|a, b| a.query.name.cmp(&b.query.name));
+                entries
+        }
+
+        /// Name collisions met while loading, in load order
+        pub fn conflicts(&self) -> &[Conflict] {
+                &self.conflicts
+        }
+}
+
+fn is_sql(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("sql"))
+}
@@ -0,0 +1,144 @@
+//! `.sql` files with a metadata header
+//!
+//! Leading `--` comment lines may annotate the query:
+//! ```sql
+//! -- name: students_id_and_name
+//! -- description: students above an id whose first name contains a fragment
+//! -- param: min_id INT = 0 "lowest id (exclusive)"
+//! -- param: name_fragment VARCHAR "part of the first name"
+//! SELECT ... WHERE StudentID > ? AND FirstName LIKE CONCAT('%', ?, '%');
+//! ```
+//! `-- param: <name> <TYPE> [= <default>] ["<description>"]`, one per `?`, in order.
+//! Other comment lines in the header are ignored (e.g. `-- returns:`, read by `proc_macro::sql_queries!`);
+//! the header ends at the first non-comment line.
+//! Every field is optional, so bare SQL files are still valid `QueryFile`s.
+
+use std::path::Path;
+
+use derive_more::Display;
+
+use crate::params::ParamKind;
+
+/// A named `?` parameter declared in a query file header
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParam {
+        pub name:        String,
+        /// MySQL type as written, e.g. `INT`, `VARCHAR(100)`
+        pub sql_type:    String,
+        pub kind:        ParamKind,
+        pub default:     Option<String>,
+        pub description: Option<String>,
+}
+
+/// A query file: header metadata & the SQL itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryFile {
+        /// `-- name:`, else the file stem
+        pub name:        String,
+        pub description: Option<String>,
+        /// empty when the header declares none (parameters may still exist, unannotated)
+        pub params:      Vec<QueryParam>,
+        /// full file contents (MySQL ignores the header comments)
+        pub sql:         String,
+}
+
+/// A header line that didn't parse, or a header that doesn't fit its statement
+#[derive(Debug, Display, Clone, PartialEq)]
+#[display("{query}{}: {message}", line.map(|l| format!(" (line {l})")).unwrap_or_default())]
+pub struct QueryFileError {
+        pub query:   String,
+        pub line:    Option<usize>,
+        pub message: String,
+}
+impl std::error::Error for QueryFileError {}
+
+impl QueryFile {
+        /// Parses the header of `sql`; `path` supplies the fallback name
+        pub fn parse(path: &Path, sql: &str) -> Result<Self, QueryFileError> {
+                let stem = path
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                let mut query = Self {
+                        name:        stem,
+                        description: None,
+                        params:      Vec::new(),
+                        sql:         sql.to_string(),
+                };
+
+                for (i, line) in sql.lines().enumerate() {
+                        let line = line.trim();
+                        if line.is_empty() {
+                                continue;
+                        }
+                        let Some(comment) = line.strip_prefix("--") else { break };
+                        let Some((key, value)) = comment.split_once(':') else { continue };
+                        let value = value.trim();
+                        let error = |message: String| QueryFileError {
+                                query: query.name.clone(),
+                                line: Some(i + 1),
+                                message,
+                        };
+                        match key.trim() {
+                                "name" if value.is_empty() => return Err(error("empty `name`".into())),
+                                "name" => query.name = value.to_string(),
+                                "description" => query.description = Some(value.to_string()),
+                                "param" => {
+                                        let param = QueryParam::parse(value).map_err(error)?;
+                                        if query.params.iter().any(|p| p.name == param.name) {
+                                                return Err(error(format!("duplicate param `{}`", param.name)));
+                                        }
+                                        query.params.push(param);
+                                }
+                                _ => {}
+                        }
+                }
+                Ok(query)
+        }
+
+        /// Declared params must match the prepared statement's `?` count (unless none are declared)
+        pub fn validate(&self, param_count: usize) -> Result<(), QueryFileError> {
+                if self.params.is_empty() || self.params.len() == param_count {
+                        return Ok(());
+                }
+                Err(QueryFileError {
+                        query:   self.name.clone(),
+                        line:    None,
+                        message: format!(
+                                "header declares {} param(s) but the statement has {param_count}",
+                                self.params.len()
+                        ),
+                })
+        }
+}
+
+impl QueryParam {
+        /// `<name> <TYPE> [= <default>] ["<description>"]`
+        fn parse(spec: &str) -> Result<Self, String> {
+                let (spec, description) = match spec.split_once('"') {
+                        Some((head, quoted)) => {
+                                let description = quoted
+                                        .strip_suffix('"')
+                                        .ok_or_else(|| format!("unterminated description in `{spec}`"))?;
+                                (head.trim(), Some(description.to_string()))
+                        }
+                        None => (spec, None),
+                };
+                let (spec, default) = match spec.split_once('=') {
+                        Some((head, default)) => (head.trim(), Some(default.trim().to_string())),
+                        None => (spec, None),
+                };
+                let mut words = spec.split_whitespace();
+                let name = words.next().ok_or("param needs a name and a type")?.to_string();
+                let sql_type = words.collect::<Vec<_>>().join(" ");
+                if sql_type.is_empty() {
+                        return Err(format!("param `{name}` needs a type (e.g. INT, VARCHAR, DATE)"));
+                }
+                let kind = ParamKind::from_type_name(&sql_type);
+                if let Some(default) = &default {
+                        kind.parse(default)
+                                .map_err(|e| format!("param `{name}` default: {e}"))?;
+                }
+                Ok(Self { name, sql_type, kind, default, description })
+        }
+}
@@ -0,0 +1,371 @@
+//! Shape of the connected database, read from `information_schema` (or, offline, from its DDL: see `ddl`)
+//!
+//! One model (`Schema` > `Table` > `Column`, keys & indexes) for everything that needs to know the tables
+//! beyond hand-written structs: codegen, validation, docs.
+//! `Display` renders it as `tabled` tables, `serde` as JSON.
+//!
+//! MySQL only: the `information_schema` columns used here (`COLUMN_TYPE`, `EXTRA`, comments) are MySQL's.
+
+use std::fmt;
+
+use polars::prelude::{DataType, Schema as PolarsSchema};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, MySqlPool};
+use tabled::{Table as TextTable, Tabled, settings::Style};
+use tracing::instrument;
+
+use crate::{enums::{enum_dtype, parse_enum_values},
+            stream_to_df::mysql_column_kind};
+
+/// Rule of a foreign key declared without `ON UPDATE` / `ON DELETE`, as MySQL 8 reports it
+pub const DEFAULT_REFERENCE_RULE: &str = "NO ACTION";
+
+/// Tables of one database, by name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schema {
+        pub database: String,
+        pub tables:   Vec<Table>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table {
+        pub name:         String,
+        pub comment:      Option<String>,
+        /// in declared order
+        pub columns:      Vec<Column>,
+        /// primary key columns, in key order (empty if there's none)
+        pub primary_key:  Vec<String>,
+        pub foreign_keys: Vec<ForeignKey>,
+        /// by name; includes `PRIMARY` and the indexes backing foreign keys
+        pub indexes:      Vec<Index>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Column {
+        pub name:           String,
+        /// base type, lowercase (`int`, `varchar`, `enum`)
+        pub data_type:      String,
+        /// full type as declared (`int`, `varchar(100)`, `enum('A','B')`)
+        pub column_type:    String,
+        pub nullable:       bool,
+        /// default as the server reports it (`NULL` defaults are `None`)
+        pub default:        Option<String>,
+        pub auto_increment: bool,
+        pub comment:        Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForeignKey {
+        pub name:               String,
+        pub columns:            Vec<String>,
+        pub referenced_table:   String,
+        /// same length & order as `columns`
+        pub referenced_columns: Vec<String>,
+        /// `ON UPDATE` rule (`RESTRICT`, `CASCADE`, ...)
+        pub on_update:          String,
+        /// `ON DELETE` rule
+        pub on_delete:          String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Index {
+        pub name:    String,
+        /// in index order
+        pub columns: Vec<String>,
+        pub unique:  bool,
+}
+
+impl Schema {
+        /// `tables` (all base tables if empty) of the connection's current database, sorted by name
+        #[instrument(name = "load_schema", level = "debug", skip(pool))]
+        pub async fn load(pool: &MySqlPool, tables: &[String]) -> Result<Self, sqlx::Error> {
+                let database: Option<String> = sqlx::query_scalar("SELECT DATABASE()").fetch_one(pool).await?;
+                let database = database.ok_or_else(|| {
+                        sqlx::Error::Configuration("no database selected; add one to the URL (`.../university`)".into())
+                })?;
+                let wanted = |table: &str| tables.is_empty() || tables.iter().any(|t| t.eq_ignore_ascii_case(table));
+
+                let table_rows: Vec<TableRow> = sqlx::query_as(
+                        "SELECT TABLE_NAME AS name, TABLE_COMMENT AS comment FROM information_schema.TABLES
+                         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE'
+                         ORDER BY TABLE_NAME",
+                )
+                .fetch_all(pool)
+                .await?;
+                let column_rows: Vec<ColumnRow> = sqlx::query_as(
+                        "SELECT TABLE_NAME AS table_name, COLUMN_NAME AS name, DATA_TYPE AS data_type,
+                                COLUMN_TYPE AS column_type, IS_NULLABLE AS is_nullable, COLUMN_DEFAULT AS `default`,
+                                EXTRA AS extra, COLUMN_COMMENT AS comment
+                         FROM information_schema.COLUMNS
+                         WHERE TABLE_SCHEMA = DATABASE()
+                         ORDER BY TABLE_NAME, ORDINAL_POSITION",
+                )
+                .fetch_all(pool)
+                .await?;
+                let key_rows: Vec<KeyRow> = sqlx::query_as(
+                        "SELECT k.TABLE_NAME AS table_name, k.CONSTRAINT_NAME AS constraint_name,
+                                k.COLUMN_NAME AS column_name, k.REFERENCED_TABLE_NAME AS referenced_table,
+                                k.REFERENCED_COLUMN_NAME AS referenced_column,
+                                r.UPDATE_RULE AS on_update, r.DELETE_RULE AS on_delete
+                         FROM information_schema.KEY_COLUMN_USAGE k
+                         LEFT JOIN information_schema.REFERENTIAL_CONSTRAINTS r
+                                ON r.CONSTRAINT_SCHEMA = k.CONSTRAINT_SCHEMA
+                               AND r.TABLE_NAME = k.TABLE_NAME
+                               AND r.CONSTRAINT_NAME = k.CONSTRAINT_NAME
+                         WHERE k.TABLE_SCHEMA = DATABASE()
+                         ORDER BY k.TABLE_NAME, k.CONSTRAINT_NAME, k.ORDINAL_POSITION",
+                )
+                .fetch_all(pool)
+                .await?;
+                // NON_UNIQUE is INT on 8.x, BIGINT on 5.7; functional index parts have no COLUMN_NAME
+                let index_rows: Vec<IndexRow> = sqlx::query_as(
+                        "SELECT TABLE_NAME AS table_name, INDEX_NAME AS name, COLUMN_NAME AS column_name,
+                                CAST(NON_UNIQUE AS SIGNED) AS non_unique
+                         FROM information_schema.STATISTICS
+                         WHERE TABLE_SCHEMA = DATABASE()
+                         ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX",
+                )
+                .fetch_all(pool)
+                .await?;
+
+                let mut schema = Self {
+                        database,
+                        tables: table_rows
+                                .into_iter()
+                                .filter(|row| wanted(&row.name))
+                                .map(|row| Table::new(row.name, non_empty(row.comment)))
+                                .collect(),
+                };
+                for row in column_rows {
+                        if let Some(table) = schema.table_mut(&row.table_name) {
+                                table.columns.push(row.into());
+                        }
+                }
+                for row in key_rows {
+                        if let Some(table) = schema.table_mut(&row.table_name) {
+                                table.add_key_column(row);
+                        }
+                }
+                for row in index_rows {
+                        let Some(column) = row.column_name else { continue };
+                        if let Some(table) = schema.table_mut(&row.table_name) {
+                                table.add_index_column(row.name, column, row.non_unique == 0);
+                        }
+                }
+                Ok(schema)
+        }
+
+        pub fn table(&self, name: &str) -> Option<&Table> {
+                self.tables.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+        }
+
+        fn table_mut(&mut self, name: &str) -> Option<&mut Table> {
+                self.tables.iter_mut().find(|t| t.name.eq_ignore_ascii_case(name))
+        }
+}
+
+impl Table {
+        pub fn new(name: String, comment: Option<String>) -> Self {
+                Self {
+                        name,
+                        comment,
+                        columns: Vec::new(),
+                        primary_key: Vec::new(),
+                        foreign_keys: Vec::new(),
+                        indexes: Vec::new(),
+                }
+        }
+
+        pub fn column(&self, name: &str) -> Option<&Column> {
+                self.columns.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+        }
+
+        /// dtypes a `SELECT *` of this table reads into (see `stream_to_df`)
+        pub fn polars_schema(&self) -> PolarsSchema {
+                self.columns
+                        .iter()
+                        .map(|c| (c.name.as_str().into(), c.polars_dtype()))
+                        .collect()
+        }
+
+        /// Foreign key `column` is (the first column of), if any
+        pub fn foreign_key_of(&self, column: &str) -> Option<&ForeignKey> {
+                self.foreign_keys
+                        .iter()
+                        .find(|fk| fk.columns.first().is_some_and(|c| c.eq_ignore_ascii_case(column)))
+        }
+
+        /// One `KEY_COLUMN_USAGE` row: part of the primary key, a foreign key, or a unique constraint (covered by
+        /// `indexes`)
+        fn add_key_column(&mut self, row: KeyRow) {
+                if row.constraint_name == "PRIMARY" {
+                        self.primary_key.push(row.column_name);
+                        return;
+                }
+                let (Some(referenced_table), Some(referenced_column)) = (row.referenced_table, row.referenced_column)
+                else {
+                        return;
+                };
+                match self.foreign_keys.iter_mut().find(|fk| fk.name == row.constraint_name) {
+                        Some(fk) => {
+                                fk.columns.push(row.column_name);
+                                fk.referenced_columns.push(referenced_column);
+                        }
+                        None => self.foreign_keys.push(ForeignKey {
+                                name: row.constraint_name,
+                                columns: vec![row.column_name],
+                                referenced_table,
+                                referenced_columns: vec![referenced_column],
+                                on_update: row.on_update.unwrap_or_else(|| DEFAULT_REFERENCE_RULE.to_string()),
+                                on_delete: row.on_delete.unwrap_or_else(|| DEFAULT_REFERENCE_RULE.to_string()),
+                        }),
+                }
+        }
+
+        fn add_index_column(&mut self, name: String, column: String, unique: bool) {
+                match self.indexes.iter_mut().find(|index| index.name == name) {
+                        Some(index) => index.columns.push(column),
+                        None => self.indexes.push(Index { name, columns: vec![column], unique }),
+                }
+        }
+
+        /// `PRI`, `FK students.StudentID`, both, or empty
+        fn key_label(&self, column: &str) -> String {
+                let primary = self.primary_key.iter().any(|c| c.eq_ignore_ascii_case(column));
+                let foreign = self.foreign_keys.iter().find_map(|fk| {
+                        let i = fk.columns.iter().position(|c| c.eq_ignore_ascii_case(column))?;
+                        Some(format!("FK {}.{}", fk.referenced_table, fk.referenced_columns[i]))
+                });
+                match (primary, foreign) {
+                        (true, Some(fk)) => format!("PRI, {fk}"),
+                        (true, None) => "PRI".to_string(),
+                        (false, Some(fk)) => fk,
+                        (false, None) => String::new(),
+                }
+        }
+}
+
+impl Column {
+        /// Declared values of an `enum(...)` column
+        pub fn enum_values(&self) -> Option<Vec<String>> {
+                parse_enum_values(&self.column_type)
+        }
+
+        /// dtype `stream_to_df` reads this column as: `Enum` of the declared values, or what the sqlx type name maps
+        /// to (`tinyint(1)` being `BOOLEAN`)
+        pub fn polars_dtype(&self) -> DataType {
+                if let Some(values) = self.enum_values() {
+                        return enum_dtype(&values);
+                }
+                let name = match self.column_type.as_str() {
+                        "tinyint(1)" => "BOOLEAN".to_string(),
+                        ty if ty.contains("unsigned") => format!("{} UNSIGNED", self.data_type.to_uppercase()),
+                        _ => self.data_type.to_uppercase(),
+                };
+                mysql_column_kind(&name).dtype()
+        }
+}
+
+/// One `tabled` table per table: its columns, then keys & indexes underneath
+impl fmt::Display for Schema {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                writeln!(f, "database: {}", self.database)?;
+                for table in &self.tables {
+                        writeln!(f)?;
+                        writeln!(f, "{table}")?;
+                }
+                Ok(())
+        }
+}
+
+impl fmt::Display for Table {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self.comment {
+                        Some(comment) => writeln!(f, "{} -- {comment}", self.name)?,
+                        None => writeln!(f, "{}", self.name)?,
+                }
+                let rows = self.columns.iter().map(|column| ColumnLine {
+                        column:  column.name.clone(),
+                        ty:      column.column_type.clone(),
+                        null:    if column.nullable { "YES" } else { "NO" },
+                        default: column.default.clone().unwrap_or_default(),
+                        key:     self.key_label(&column.name),
+                        extra:   if column.auto_increment { "auto_increment" } else { "" },
+                        comment: column.comment.clone().unwrap_or_default(),
+                });
+                write!(f, "{}", TextTable::new(rows).with(Style::rounded()))?;
+                for index in &self.indexes {
+                        let kind = if index.unique { "unique index" } else { "index" };
+                        write!(f, "\n{kind} {} ({})", index.name, index.columns.join(", "))?;
+                }
+                Ok(())
+        }
+}
+
+/// A `Display` line of a table's columns
+#[derive(Tabled)]
+struct ColumnLine {
+        column:  String,
+        #[tabled(rename = "type")]
+        ty:      String,
+        null:    &'static str,
+        default: String,
+        key:     String,
+        extra:   &'static str,
+        comment: String,
+}
+
+#[derive(FromRow)]
+struct TableRow {
+        name:    String,
+        comment: Option<String>,
+}
+
+#[derive(FromRow)]
+struct ColumnRow {
+        table_name:  String,
+        name:        String,
+        data_type:   String,
+        column_type: String,
+        is_nullable: String,
+        default:     Option<String>,
+        extra:       String,
+        comment:     Option<String>,
+}
+impl From<ColumnRow> for Column {
+        fn from(row: ColumnRow) -> Self {
+                Self {
+                        name:           row.name,
+                        data_type:      row.data_type.to_ascii_lowercase(),
+                        column_type:    row.column_type,
+                        nullable:       row.is_nullable.eq_ignore_ascii_case("YES"),
+                        default:        row.default,
+                        auto_increment: row.extra.to_ascii_lowercase().contains("auto_increment"),
+                        comment:        non_empty(row.comment),
+                }
+        }
+}
+
+#[derive(FromRow)]
+struct KeyRow {
+        table_name:        String,
+        constraint_name:   String,
+        column_name:       String,
+        referenced_table:  Option<String>,
+        referenced_column: Option<String>,
+        on_update:         Option<String>,
+        on_delete:         Option<String>,
+}
+
+#[derive(FromRow)]
+struct IndexRow {
+        table_name:  String,
+        name:        String,
+        column_name: Option<String>,
+        non_unique:  i64,
+}
+
+/// `information_schema` reports "no comment" as `''`
+fn non_empty(comment: Option<String>) -> Option<String> {
+        comment.filter(|c| !c.is_empty())
+}
@@ -0,0 +1,123 @@
+//! Tracing setup shared by every bin
+//!
+//! - stderr logs, level from `-v/-q` (warn by default; `RUST_LOG` wins when set)
+//! - `--log-json`: also JSON lines into `.output/logging/<bin>.<date>.json` (always at debug)
+//! - `--flame`: folded stacks into `.output/profiling/<bin>.folded`, rendered to `<bin>.svg` on exit
+//!
+//! Spans close with their `time.busy`/`time.idle`, so every instrumented step reports how long it took.
+
+use std::{fs::File,
+          io::BufWriter,
+          path::{Path, PathBuf}};
+
+use clap::Args;
+use clap_verbosity_flag::{Verbosity, WarnLevel};
+use tracing_appender::{non_blocking::WorkerGuard,
+                       rolling::{RollingFileAppender, Rotation}};
+use tracing_flame::{FlameLayer, FlushGuard};
+use tracing_subscriber::{EnvFilter, Layer, filter::LevelFilter, fmt::format::FmtSpan, layer::SubscriberExt,
+                         util::SubscriberInitExt};
+
+use crate::error::{Error, Result};
+
+/// where log & profiling files go (relative to the working directory, like the justfile's `perf` recipe)
+pub const OUTPUT_DIR: &str = ".output";
+
+/// Clap args: verbosity & optional log/flamegraph files
+#[derive(Args, Debug, Default)]
+pub struct TelemetryArgs {
+        #[command(flatten)]
+        pub verbosity: Verbosity<WarnLevel>,
+        /// also write JSON logs to `.output/logging/`
+        #[arg(long, global = true)]
+        pub log_json:  bool,
+        /// record spans as a flamegraph in `.output/profiling/`
+        #[arg(long, global = true)]
+        pub flame:     bool,
+}
+
+/// Keep alive for the whole run: dropping it flushes the log file & writes the flamegraph
+#[must_use = "dropping the guard stops file logging & profiling"]
+pub struct TelemetryGuard {
+        _log:  Option<WorkerGuard>,
+        flame: Option<(FlushGuard<BufWriter<File>>, PathBuf)>,
+}
+
+impl TelemetryArgs {
+        /// Installs the global subscriber; `bin` names the output files
+        pub fn init(&self, bin: &str) -> Result<TelemetryGuard> {
+                let stderr_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+                        EnvFilter::default().add_directive(self.verbosity.tracing_level_filter().into())
+                });
+                let stderr = tracing_subscriber::fmt::layer()
+                        .with_writer(std::io::stderr)
+                        .with_span_events(FmtSpan::CLOSE)
+                        .with_filter(stderr_filter);
+
+                let (json, log) = if self.log_json {
+                        let dir = output_dir("logging")?;
+                        let appender = RollingFileAppender::builder()
+                                .rotation(Rotation::DAILY)
+                                .filename_prefix(bin)
+                                .filename_suffix("json")
+                                .build(&dir)
+                                .map_err(|e| io_error(&dir, std::io::Error::other(e)))?;
+                        let (writer, guard) = tracing_appender::non_blocking(appender);
+                        let layer = tracing_subscriber::fmt::layer()
+                                .json()
+                                .with_writer(writer)
+                                .with_span_events(FmtSpan::CLOSE)
+                                .with_filter(LevelFilter::DEBUG);
+                        (Some(layer), Some(guard))
+                } else {
+                        (None, None)
+                };
+
+                let (flame_layer, flame) = if self.flame {
+                        let folded = output_dir("profiling")?.join(format!("{bin}.folded"));
+                        let (layer, guard) = FlameLayer::with_file(&folded)
+                                .map_err(|e| io_error(&folded, std::io::Error::other(e)))?;
+                        (Some(layer.with_threads_collapsed(true)), Some((guard, folded)))
+                } else {
+                        (None, None)
+                };
+
+                tracing_subscriber::registry()
+                        .with(stderr)
+                        .with(json)
+                        .with(flame_layer)
+                        .try_init()?;
+                Ok(TelemetryGuard { _log: log, flame })
+        }
+}
+
+impl Drop for TelemetryGuard {
+        fn drop(&mut self) {
+                let Some((guard, folded)) = self.flame.take() else { return };
+                let svg = folded.with_extension("svg");
+                let rendered = guard
+                        .flush()
+                        .map_err(|e| e.to_string())
+                        .and_then(|()| File::create(&svg).map_err(|e| e.to_string()))
+                        .and_then(|file| {
+                                let mut options = inferno::flamegraph::Options::default();
+                                inferno::flamegraph::from_files(&mut options, &[folded.clone()], BufWriter::new(file))
+                                        .map_err(|e| e.to_string())
+                        });
+                match rendered {
+                        Ok(()) => eprintln!("flamegraph: {}", svg.display()),
+                        Err(e) => eprintln!("flamegraph not written ({}): {e}", folded.display()),
+                }
+        }
+}
+
+/// `.output/<sub>`, created if missing
+fn output_dir(sub: &str) -> Result<PathBuf> {
+        let dir = Path::new(OUTPUT_DIR).join(sub);
+        std::fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+        Ok(dir)
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> Error {
+        Error::Io { path: path.to_path_buf(), source }
+}
@@ -0,0 +1,148 @@
+//! `#[derive(ToDataFrame)]` / `#[derive(FromDataFrame)]` on `StudentQA`-shaped structs
+
+use chrono::NaiveDate;
+use polars::prelude::*;
+use proc_macro::{FromDataFrame, ToDataFrame};
+use xp_sqlx::enums::{Grade, School};
+
+/// Field names the generated code could shadow
+#[derive(Debug, Clone, PartialEq, ToDataFrame, FromDataFrame)]
+struct Edge {
+        row:    i32,
+        rows:   String,
+        len:    Option<f64>,
+        df:     u32,
+        series: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, ToDataFrame, FromDataFrame)]
+#[df(rename_all = "PascalCase")]
+struct Student {
+        #[df(rename = "StudentID")]
+        id:         i32,
+        first_name: String,
+        #[df(rename = "DateOfBirth")]
+        dob:        Option<NaiveDate>,
+        #[df(sql_enum)]
+        school:     Option<School>,
+        #[df(sql_enum)]
+        grade:      Grade,
+        #[df(skip)]
+        email:      Option<String>,
+}
+
+/// Columns cast after they're built
+#[derive(Debug, ToDataFrame)]
+struct Cast {
+        #[df(dtype = "i64")]
+        wide:   i32,
+        #[df(dtype = "u32")]
+        offset: i32,
+}
+
+fn students() -> Vec<Student> {
+        vec![
+                Student {
+                        id:         1,
+                        first_name: "John".to_string(),
+                        dob:        NaiveDate::from_ymd_opt(1998, 4, 23),
+                        school:     Some(School::Sciences),
+                        grade:      Grade::B,
+                        email:      Some("john.doe@example.com".to_string()),
+                },
+                Student {
+                        id:         2,
+                        first_name: "Jane".to_string(),
+                        dob:        None,
+                        school:     None,
+                        grade:      Grade::Incomplete,
+                        email:      None,
+                },
+        ]
+}
+
+fn edges() -> Vec<Edge> {
+        vec![Edge { row: 1, rows: "a".to_string(), len: Some(0.5), df: 7, series: true }, Edge {
+                row:    2,
+                rows:   "b".to_string(),
+                len:    None,
+                df:     8,
+                series: false,
+        }]
+}
+
+#[test]
+fn borrowed_and_owned_rows_give_the_same_frame() {
+        let rows = edges();
+        let borrowed = Edge::to_dataframe(&rows).unwrap();
+        let owned = Edge::into_dataframe(rows).unwrap();
+        assert!(borrowed.equals_missing(&owned));
+        assert_eq!(owned.shape(), (2, 5));
+        assert_eq!(owned.get_column_names(), ["row", "rows", "len", "df", "series"]);
+        assert_eq!(owned.column("row").unwrap().i32().unwrap().get(1), Some(2));
+        assert_eq!(owned.column("len").unwrap().null_count(), 1);
+}
+
+#[test]
+fn edge_names_round_trip() {
+        let df = Edge::to_dataframe(&edges()).unwrap();
+        assert_eq!(Edge::from_dataframe(&df).unwrap(), edges());
+}
+
+#[test]
+fn students_round_trip() {
+        let df = Student::to_dataframe(&students()).unwrap();
+        assert_eq!(df.get_column_names(), ["StudentID", "FirstName", "DateOfBirth", "School", "Grade"]);
+        assert_eq!(df.column("DateOfBirth").unwrap().dtype(), &DataType::Date);
+        assert_eq!(df.column("School").unwrap().dtype(), &School::polars_dtype());
+        assert_eq!(df.column("Grade").unwrap().dtype(), &Grade::polars_dtype());
+
+        let back = Student::from_dataframe(&df).unwrap();
+        // skipped fields come back as `Default::default()`
+        let expected: Vec<_> = students().into_iter().map(|s| Student { email: None, ..s }).collect();
+        assert_eq!(back, expected);
+}
+
+#[test]
+fn enum_columns_may_be_strings() {
+        let mut df = Student::to_dataframe(&students()).unwrap();
+        df.apply("School", |c| c.cast(&DataType::String).unwrap()).unwrap();
+        assert_eq!(Student::from_dataframe(&df).unwrap()[0].school, Some(School::Sciences));
+}
+
+#[test]
+fn null_into_a_required_field_names_column_and_row() {
+        let df = Student::to_dataframe(&students())
+                .unwrap()
+                .lazy()
+                .with_column(lit(NULL).cast(DataType::String).alias("FirstName"))
+                .collect()
+                .unwrap();
+        let e = Student::from_dataframe(&df).unwrap_err().to_string();
+        assert!(e.contains("Student: column `FirstName` row 0 is null"), "{e}");
+}
+
+#[test]
+fn wrong_dtype_is_a_schema_mismatch() {
+        let mut df = Student::to_dataframe(&students()).unwrap();
+        df.apply("StudentID", |c| c.cast(&DataType::Int64).unwrap()).unwrap();
+        let e = Student::from_dataframe(&df).unwrap_err();
+        assert!(matches!(e, PolarsError::SchemaMismatch(_)), "{e}");
+        assert!(e.to_string().contains("column `StudentID` has dtype i64"), "{e}");
+}
+
+#[test]
+fn unknown_enum_value_is_an_error() {
+        let mut df = Student::to_dataframe(&students()).unwrap();
+        df.with_column(Series::new("Grade".into(), ["B", "Z"])).unwrap();
+        assert!(Student::from_dataframe(&df).is_err());
+}
+
+#[test]
+fn dtype_casts_and_rejects_what_does_not_fit() {
+        let df = Cast::to_dataframe(&[Cast { wide: 1, offset: 2 }]).unwrap();
+        assert_eq!(df.dtypes(), [DataType::Int64, DataType::UInt32]);
+        // -1 has no u32: an error rather than a null
+        let e = Cast::into_dataframe([Cast { wide: 1, offset: -1 }]).unwrap_err();
+        assert!(matches!(e, PolarsError::InvalidOperation(_) | PolarsError::ComputeError(_)), "{e}");
+}
@@ -0,0 +1,32 @@
+//! `ParamKind::from_type_name`, for the same SQL types `sql_queries!` gives typed arguments
+//! (see `rust_type`'s test in `proc_macro`)
+
+use xp_sqlx::params::ParamKind;
+
+#[test]
+fn sql_types_map_like_the_generated_arguments() {
+        let cases = [
+                ("INT", ParamKind::Int),
+                ("int(11)", ParamKind::Int),
+                ("TINYINT", ParamKind::Int),
+                ("YEAR", ParamKind::Int),
+                ("INT UNSIGNED", ParamKind::UInt),
+                ("smallint(5) unsigned", ParamKind::UInt),
+                ("BIGINT", ParamKind::Int),
+                ("BIGINT UNSIGNED", ParamKind::UInt),
+                ("FLOAT", ParamKind::Float),
+                ("DOUBLE", ParamKind::Float),
+                ("BOOLEAN", ParamKind::Bool),
+                ("DATE", ParamKind::Date),
+                ("DATETIME", ParamKind::Datetime),
+                ("TIMESTAMP", ParamKind::Datetime),
+                ("TIME", ParamKind::Time),
+                ("DECIMAL(10, 2)", ParamKind::Decimal),
+                ("VARCHAR(100)", ParamKind::Text),
+                ("ENUM('A','B')", ParamKind::Text),
+                ("JSON", ParamKind::Text),
+        ];
+        for (sql_type, kind) in cases {
+                assert_eq!(ParamKind::from_type_name(sql_type), kind, "{sql_type}");
+        }
+}
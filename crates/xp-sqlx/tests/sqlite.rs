@@ -0,0 +1,148 @@
+//! The demo database without a server: `sqlite::memory:` seeded with `seed_sqlite`
+
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use polars::prelude::*;
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use xp_sqlx::{bench::TransImpl,
+              connection::seed_sqlite,
+              enums::School,
+              stream_to_df::{ChunkLimits, column_name, concat_dataframe_chunks, query_to_dataframe,
+                             query_to_dataframe_chunks, sql_to_dataframe}};
+
+/// A fresh copy of the demo tables; one connection, as each in-memory connection is its own database
+async fn seeded() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect("sqlite::memory:")
+                .await
+                .unwrap();
+        assert!(seed_sqlite(&pool).await.unwrap());
+        pool
+}
+
+#[tokio::test]
+async fn seeding_twice_is_a_no_op() {
+        let pool = seeded().await;
+        assert!(!seed_sqlite(&pool).await.unwrap());
+        let df = sql_to_dataframe(&pool, "SELECT * FROM students").await.unwrap();
+        assert_eq!(df.height(), 63);
+}
+
+#[tokio::test]
+async fn tables_read_with_their_dtypes() {
+        let pool = seeded().await;
+        let cases: [(&str, usize, &[DataType]); 4] = [
+                ("students", 63, &[
+                        DataType::Int64,
+                        DataType::String,
+                        DataType::String,
+                        DataType::Date,
+                        DataType::String,
+                        DataType::String,
+                ]),
+                ("courses", 3, &[DataType::Int64, DataType::String, DataType::String, DataType::Int64]),
+                ("professors", 3, &[
+                        DataType::Int64,
+                        DataType::String,
+                        DataType::String,
+                        DataType::String,
+                        DataType::String,
+                ]),
+                ("enrollments", 6, &[
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Date,
+                        DataType::String,
+                ]),
+        ];
+        for (table, rows, dtypes) in cases {
+                let df = sql_to_dataframe(&pool, &format!("SELECT * FROM {table}"))
+                        .await
+                        .unwrap();
+                assert_eq!(df.height(), rows, "{table}");
+                assert_eq!(df.dtypes(), dtypes, "{table}");
+        }
+}
+
+#[tokio::test]
+async fn bound_query_and_empty_result_keep_the_schema() {
+        let pool = seeded().await;
+        let query = "SELECT StudentID, DateOfBirth, School FROM students WHERE StudentID <= ?";
+        let df = query_to_dataframe(&pool, sqlx::query(query).bind(5)).await.unwrap();
+        assert_eq!(df.shape(), (5, 3));
+        assert_eq!(df.column("School").unwrap().str().unwrap().get(0), Some("Sciences"));
+
+        let empty = query_to_dataframe(&pool, sqlx::query(query).bind(0)).await.unwrap();
+        assert_eq!(empty.height(), 0);
+        assert_eq!(empty.get_column_names(), ["StudentID", "DateOfBirth", "School"]);
+}
+
+#[tokio::test]
+async fn chunks_add_up_to_the_table() {
+        let pool = seeded().await;
+        let chunks: Vec<DataFrame> =
+                query_to_dataframe_chunks(&pool, sqlx::query("SELECT * FROM students"), ChunkLimits::rows(25))
+                        .try_collect()
+                        .await
+                        .unwrap();
+        assert_eq!(chunks.iter().map(DataFrame::height).collect::<Vec<_>>(), [25, 25, 13]);
+
+        let chunks = query_to_dataframe_chunks(&pool, sqlx::query("SELECT * FROM students"), ChunkLimits::rows(25));
+        let whole = concat_dataframe_chunks(chunks).await.unwrap();
+        let direct = sql_to_dataframe(&pool, "SELECT * FROM students").await.unwrap();
+        assert!(whole.equals_missing(&direct));
+}
+
+#[tokio::test]
+async fn query_files_run_with_their_type_overrides_stripped() {
+        // `School as "school: _"` is for `query_file_as!`; at runtime (`choose_query`) the column is just `school`
+        let pool = seeded().await;
+        let df = sql_to_dataframe(&pool, include_str!("../../../data/sql_queries/students_10.sql"))
+                .await
+                .unwrap();
+        assert_eq!(df.get_column_names(), ["id", "first_name", "last_name", "dob", "school", "email"]);
+        assert_eq!(column_name("school: _"), "school");
+        assert_eq!(column_name("id!"), "id");
+        assert_eq!(column_name("email?: String"), "email");
+        assert_eq!(column_name("'a:' || b"), "'a:' || b");
+}
+
+#[tokio::test]
+async fn chunks_need_only_the_one_connection() {
+        // `seeded` is a pool of one: a lookup on a second connection would wait for the stream to end
+        let pool = seeded().await;
+        let chunks = query_to_dataframe_chunks(&pool, sqlx::query("SELECT * FROM enrollments"), ChunkLimits::rows(40));
+        let df = tokio::time::timeout(Duration::from_secs(5), concat_dataframe_chunks(chunks))
+                .await
+                .expect("the stream waited on a second connection")
+                .unwrap();
+        assert_eq!(
+                df.height(),
+                sql_to_dataframe(&pool, "SELECT * FROM enrollments")
+                        .await
+                        .unwrap()
+                        .height()
+        );
+}
+
+#[tokio::test]
+async fn every_transpose_gives_the_same_frame() {
+        let pool = seeded().await;
+        let mut frames = Vec::new();
+        for implementation in [TransImpl::VOfStruct, TransImpl::StructOfV, TransImpl::SeriesToDF, TransImpl::Builders] {
+                let (df, cost) = implementation.run(&pool, 2).await.unwrap();
+                assert_eq!(cost.rows, 126, "{implementation}");
+                assert_eq!(df.shape(), (126, 6), "{implementation}");
+                assert_eq!(df.column("school").unwrap().dtype(), &School::polars_dtype(), "{implementation}");
+                frames.push(df);
+        }
+        // column names differ between the implementations, the values don't
+        for df in &frames[1..] {
+                for (a, b) in df.get_columns().iter().zip(frames[0].get_columns()) {
+                        assert!(a.as_materialized_series().equals_missing(b.as_materialized_series()), "{a:?} {b:?}");
+                }
+        }
+}
@@ -1,8 +1,11 @@
+-- name: students_10
+-- description: first ten students
+-- returns: StudentQA
 SELECT StudentID as id, 
        FirstName as first_name, 
        LastName as last_name, 
        DateOfBirth as dob, 
-       School as school, 
+       School as "school: _", 
        Email as email
 FROM students
 LIMIT 10;
@@ -1,3 +1,7 @@
+-- name: students_id_and_name
+-- description: students above an id whose first name contains a fragment
+-- param: min_id INT = 0 "lowest id (exclusive)"
+-- param: name_fragment VARCHAR(100) "part of the first name"
 SELECT StudentID as id, 
        FirstName as first_name, 
        LastName as last_name, 
@@ -1,8 +1,12 @@
+-- name: students_w_id
+-- description: one student by id
+-- returns: StudentQA
+-- param: student_id INT = 1 "StudentID"
 SELECT StudentID as id, 
        FirstName as first_name, 
        LastName as last_name, 
        DateOfBirth as dob, 
-       School as school, 
+       School as "school: _", 
        Email as email
 FROM students 
 WHERE StudentID = ?;
@@ -0,0 +1,453 @@
+//! `gen-models`: a `Schema` ~~> one Rust module of row structs
+//!
+//! Per table: a row struct (`FromRow`, `ToDataFrame`, `Display`, `#[sqlx(rename)]` wherever the field name isn't the
+//! column name), its columnar companion `VecOf<Row>` (`push`, `Extend`, `FromIterator`, `into_dataframe`)
+//! and a `SqlEnum` per ENUM column, unless `KNOWN_ENUMS` has one with the same values: that's imported instead.
+//!
+//! Output depends on the schema alone (tables & enums by name, columns in declared order; no timestamps),
+//! and is already `cargo fmt`ed, so regenerating an unchanged schema is a no-op diff.
+
+use std::{collections::{BTreeMap, BTreeSet},
+          error::Error,
+          fmt::{self, Write as _}};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use xp_sqlx::{enums::{Grade, School},
+              schema::{Column, Schema, Table}};
+
+/// A column (or name) the generator has no Rust for
+#[derive(Debug)]
+pub struct GenError {
+        pub table:   String,
+        pub column:  String,
+        pub problem: String,
+}
+impl fmt::Display for GenError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "`{}`.`{}`: {}", self.table, self.column, self.problem)
+        }
+}
+impl Error for GenError {}
+
+/// A `SqlEnum` the models' crate (`xp-sqlx`) already has: ENUM columns with exactly its values use it
+pub struct KnownEnum {
+        /// as imported into the models module
+        pub path:   &'static str,
+        pub values: &'static [&'static str],
+}
+
+pub const KNOWN_ENUMS: &[KnownEnum] = &[KnownEnum { path: "crate::enums::Grade", values: Grade::NAMES }, KnownEnum {
+        path:   "crate::enums::School",
+        values: School::NAMES,
+}];
+
+/// The module source for `schema`'s tables
+pub fn render(schema: &Schema) -> Result<String, Box<dyn Error>> {
+        let mut models = schema.tables.iter().map(Model::new).collect::<Result<Vec<_>, _>>()?;
+        let enums = assign_enums(&mut models, KNOWN_ENUMS)?;
+
+        let fields = || models.iter().flat_map(|m| &m.fields);
+        let chrono: BTreeSet<_> = fields().filter_map(|f| f.rust.chrono_import()).collect();
+        let imported: Vec<_> = enums.values().filter_map(|declared| declared.import).collect();
+        let has_enums = enums.values().any(|declared| declared.import.is_none());
+        let has_nullable = fields().any(|f| f.nullable);
+
+        let mut out = String::new();
+        render_header(&mut out, &schema.database, chrono, &imported, has_enums)?;
+        for (name, declared) in enums.iter().filter(|(_, declared)| declared.import.is_none()) {
+                render_enum(&mut out, name, declared)?;
+        }
+        for model in &models {
+                render_model(&mut out, model)?;
+        }
+        if has_nullable {
+                out.push_str(OR_NULL);
+        }
+        Ok(out)
+}
+
+/// Module docs & imports
+fn render_header(
+        out: &mut String,
+        database: &str,
+        chrono: BTreeSet<&str>,
+        imported: &[&str],
+        has_enums: bool,
+) -> fmt::Result {
+        writeln!(out, "//! Row structs for the `{database}` tables")?;
+        writeln!(out, "//!")?;
+        writeln!(out, "//! Generated by `cargo run -p xtask -- gen-models`; regenerate rather than edit.")?;
+        writeln!(out, "//! A `<Row>` per table (`FromRow`, `ToDataFrame`, `Display`) and `VecOf<Row>`, its columns.")?;
+        writeln!(out)?;
+        writeln!(out, "use std::fmt;")?;
+        writeln!(out)?;
+        let chrono: Vec<_> = chrono.into_iter().collect();
+        match chrono.as_slice() {
+                [] => {}
+                [one] => writeln!(out, "use chrono::{one};")?,
+                many => writeln!(out, "use chrono::{{{}}};", many.join(", "))?,
+        }
+        writeln!(out, "use polars::prelude::{{DataFrame, NamedFrom, PolarsResult, Series}};")?;
+        if has_enums {
+                writeln!(out, "use proc_macro::{{SqlEnum, ToDataFrame}};")?;
+        } else {
+                writeln!(out, "use proc_macro::ToDataFrame;")?;
+        }
+        writeln!(out, "use sqlx::FromRow;")?;
+        // `crate::enums::Grade`, `crate::enums::School` ~~> `use crate::enums::{Grade, School};`
+        let mut modules: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for path in imported {
+                let (module, name) = path.rsplit_once("::").unwrap_or(("crate", path));
+                modules.entry(module).or_default().push(name);
+        }
+        if !modules.is_empty() {
+                writeln!(out)?;
+        }
+        for (module, names) in modules {
+                match names.as_slice() {
+                        [one] => writeln!(out, "use {module}::{one};")?,
+                        many => writeln!(out, "use {module}::{{{}}};", many.join(", "))?,
+                }
+        }
+        Ok(())
+}
+
+/// `Display` of an `Option` field
+const OR_NULL: &str = "
+/// An `Option` field shown as its value, or `NULL`
+struct OrNull<'a, T>(&'a Option<T>);
+impl<T: fmt::Display> fmt::Display for OrNull<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.0 {
+                        Some(value) => value.fmt(f),
+                        None => f.write_str(\"NULL\"),
+                }
+        }
+}
+";
+
+/// One table's struct
+struct Model<'a> {
+        table:  &'a Table,
+        name:   String,
+        fields: Vec<Field<'a>>,
+}
+
+struct Field<'a> {
+        column:   &'a Column,
+        ident:    String,
+        rust:     RustType,
+        nullable: bool,
+}
+
+/// A column's Rust type, `Option` aside
+enum RustType {
+        Plain(&'static str),
+        /// a `SqlEnum`: its values, and its name (left empty until `assign_enums` has seen all tables)
+        Enum {
+                values: Vec<String>,
+                name:   String,
+        },
+}
+impl RustType {
+        fn of(column: &Column) -> Option<Self> {
+                if let Some(values) = column.enum_values() {
+                        return Some(Self::Enum { values, name: String::new() });
+                }
+                let unsigned = column.column_type.contains("unsigned");
+                Some(Self::Plain(match (column.data_type.as_str(), unsigned) {
+                        ("tinyint", false) if column.column_type.starts_with("tinyint(1)") => "bool",
+                        ("tinyint" | "smallint" | "mediumint" | "int", false) => "i32",
+                        ("tinyint" | "smallint" | "mediumint" | "int", true) => "u32",
+                        ("bigint", false) => "i64",
+                        ("bigint", true) => "u64",
+                        ("float", _) => "f32",
+                        ("double" | "real", _) => "f64",
+                        ("char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" | "set", _) => "String",
+                        ("date", _) => "NaiveDate",
+                        ("datetime" | "timestamp", _) => "NaiveDateTime",
+                        ("time", _) => "NaiveTime",
+                        _ => return None,
+                }))
+        }
+
+        fn chrono_import(&self) -> Option<&'static str> {
+                match self {
+                        Self::Plain(ty @ ("NaiveDate" | "NaiveDateTime" | "NaiveTime")) => Some(ty),
+                        _ => None,
+                }
+        }
+}
+
+impl<'a> Model<'a> {
+        fn new(table: &'a Table) -> Result<Self, GenError> {
+                let error = |column: &Column, problem: String| GenError {
+                        table: table.name.clone(),
+                        column: column.name.clone(),
+                        problem,
+                };
+                let mut fields: Vec<Field> = Vec::with_capacity(table.columns.len());
+                for column in &table.columns {
+                        let rust = RustType::of(column).ok_or_else(|| {
+                                error(column, format!("no Rust type for `{}` (CAST it in a view?)", column.column_type))
+                        })?;
+                        let ident = field_ident(&column.name);
+                        if let Some(other) = fields.iter().find(|f| f.ident == ident) {
+                                return Err(error(
+                                        column,
+                                        format!("field `{ident}` is taken by `{}`", other.column.name),
+                                ));
+                        }
+                        fields.push(Field { column, ident, rust, nullable: column.nullable });
+                }
+                Ok(Self { table, name: singular(&table.name).to_upper_camel_case(), fields })
+        }
+}
+
+/// ENUM value lists by type name, each field's `RustType::Enum` named
+///
+/// A column's enum is named after the column (`School`); if that name is taken by a row struct, or by another
+/// column's enum with different values, it's prefixed with its row struct (`StudentSchool`).
+/// A `known` enum holds on to its name and is imported rather than generated, if a column has its values.
+fn assign_enums(models: &mut [Model], known: &[KnownEnum]) -> Result<BTreeMap<String, Declared>, GenError> {
+        let structs: BTreeSet<_> = models.iter().map(|m| m.name.clone()).collect();
+        let mut enums: BTreeMap<String, Declared> = known
+                .iter()
+                .map(|known| {
+                        let name = known.path.rsplit("::").next().unwrap_or(known.path);
+                        let values = known.values.iter().map(|v| v.to_string()).collect();
+                        (name.to_string(), Declared {
+                                values,
+                                variants: Vec::new(),
+                                columns: Vec::new(),
+                                import: Some(known.path),
+                        })
+                })
+                .collect();
+        for model in models.iter_mut() {
+                for field in &mut model.fields {
+                        let RustType::Enum { values, name } = &mut field.rust else { continue };
+                        *name = enum_name(&model.name, &field.column.name, values, &structs, &enums);
+                        let declared = enums.entry(name.clone()).or_insert_with(|| Declared {
+                                values:   values.clone(),
+                                variants: Vec::new(),
+                                columns:  Vec::new(),
+                                import:   None,
+                        });
+                        declared.columns
+                                .push(format!("{}.{}", model.table.name, field.column.name));
+                }
+        }
+        // known enums no column uses
+        enums.retain(|_, declared| !declared.columns.is_empty());
+        for declared in enums.values_mut().filter(|declared| declared.import.is_none()) {
+                for value in &declared.values {
+                        let variant = variant_ident(value);
+                        if declared.variants.contains(&variant) {
+                                let (table, column) = declared.columns[0].split_once('.').unwrap_or_default();
+                                return Err(GenError {
+                                        table:   table.to_string(),
+                                        column:  column.to_string(),
+                                        problem: format!("ENUM values collide as variant `{variant}`"),
+                                });
+                        }
+                        declared.variants.push(variant);
+                }
+        }
+        Ok(enums)
+}
+
+/// A generated (or imported) `SqlEnum`
+struct Declared {
+        values:   Vec<String>,
+        variants: Vec<String>,
+        /// `table.column`s typed with it
+        columns:  Vec<String>,
+        /// path of a `KnownEnum`, imported rather than generated
+        import:   Option<&'static str>,
+}
+
+fn enum_name(
+        model: &str,
+        column: &str,
+        values: &[String],
+        structs: &BTreeSet<String>,
+        enums: &BTreeMap<String, Declared>,
+) -> String {
+        let plain = column.to_upper_camel_case();
+        let free = |name: &str| {
+                !structs.contains(name) && enums.get(name).is_none_or(|declared| declared.values == values)
+        };
+        if free(&plain) {
+                return plain;
+        }
+        format!("{model}{plain}")
+}
+
+/// The enum type (or plain type) of `field`, `Option`-wrapped if nullable
+fn field_type(field: &Field) -> String {
+        let inner = match &field.rust {
+                RustType::Plain(ty) => ty,
+                RustType::Enum { name, .. } => name.as_str(),
+        };
+        if field.nullable { format!("Option<{inner}>") } else { inner.to_string() }
+}
+
+fn render_enum(out: &mut String, name: &str, declared: &Declared) -> fmt::Result {
+        let columns: Vec<_> = declared.columns.iter().map(|c| format!("`{c}`")).collect();
+        writeln!(out)?;
+        writeln!(out, "/// {}", columns.join(", "))?;
+        writeln!(out, "#[derive(SqlEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]")?;
+        writeln!(out, "pub enum {name} {{")?;
+        for (variant, value) in declared.variants.iter().zip(&declared.values) {
+                if variant != value {
+                        writeln!(out, "        #[sql_enum(rename = {value:?})]")?;
+                }
+                writeln!(out, "        {variant},")?;
+        }
+        writeln!(out, "}}")
+}
+
+fn render_model(out: &mut String, model: &Model) -> fmt::Result {
+        let name = &model.name;
+        let table = &model.table.name;
+        let types: Vec<_> = model.fields.iter().map(field_type).collect();
+        let width = model.fields.iter().map(|f| f.ident.len()).max().unwrap_or(0) + 2;
+        let field_line = |ident: &str, ty: &str| format!("        pub {:<width$}{ty},", format!("{ident}:"));
+
+        // row
+        writeln!(out)?;
+        match &model.table.comment {
+                Some(comment) => writeln!(out, "/// Row of `{table}`: {comment}")?,
+                None => writeln!(out, "/// Row of `{table}`")?,
+        }
+        writeln!(out, "#[derive(Debug, Clone, PartialEq, FromRow, ToDataFrame)]")?;
+        writeln!(out, "pub struct {name} {{")?;
+        for (field, ty) in model.fields.iter().zip(&types) {
+                if let Some(comment) = &field.column.comment {
+                        writeln!(out, "        /// {comment}")?;
+                }
+                let renamed = field.ident != field.column.name;
+                if renamed {
+                        writeln!(out, "        #[sqlx(rename = {:?})]", field.column.name)?;
+                }
+                let is_enum = matches!(field.rust, RustType::Enum { .. });
+                match (renamed, is_enum) {
+                        (true, true) => writeln!(out, "        #[df(rename = {:?}, sql_enum)]", field.column.name),
+                        (true, false) => writeln!(out, "        #[df(rename = {:?})]", field.column.name),
+                        (false, true) => writeln!(out, "        #[df(sql_enum)]"),
+                        (false, false) => Ok(()),
+                }?;
+                writeln!(out, "{}", field_line(&field.ident, ty))?;
+        }
+        writeln!(out, "}}")?;
+
+        // Display: `table(Column: value, ...)`
+        writeln!(out, "impl fmt::Display for {name} {{")?;
+        writeln!(out, "        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {{")?;
+        for (i, field) in model.fields.iter().enumerate() {
+                let label = match i {
+                        0 => format!("{table}({}: {{}}", field.column.name.replace('{', "{{").replace('}', "}}")),
+                        _ => format!(", {}: {{}}", field.column.name.replace('{', "{{").replace('}', "}}")),
+                };
+                let value = if field.nullable {
+                        format!("OrNull(&self.{})", field.ident)
+                } else {
+                        format!("self.{}", field.ident)
+                };
+                writeln!(out, "                write!(f, {label:?}, {value})?;")?;
+        }
+        let close = if model.fields.is_empty() { format!("{table}()") } else { ")".to_string() };
+        writeln!(out, "                f.write_str({close:?})")?;
+        writeln!(out, "        }}")?;
+        writeln!(out, "}}")?;
+
+        // columnar companion
+        writeln!(out)?;
+        writeln!(out, "/// `{name}`s, a Vec per column")?;
+        writeln!(out, "#[derive(Debug, Default)]")?;
+        writeln!(out, "pub struct VecOf{name} {{")?;
+        for (field, ty) in model.fields.iter().zip(&types) {
+                writeln!(out, "{}", field_line(&field.ident, &format!("Vec<{ty}>")))?;
+        }
+        writeln!(out, "}}")?;
+        writeln!(out, "impl VecOf{name} {{")?;
+        writeln!(out, "        pub fn push(&mut self, row: {name}) {{")?;
+        for field in &model.fields {
+                writeln!(out, "                self.{0}.push(row.{0});", field.ident)?;
+        }
+        writeln!(out, "        }}")?;
+        writeln!(out)?;
+        writeln!(out, "        /// One column per field, named as in the table")?;
+        writeln!(out, "        pub fn into_dataframe(self) -> PolarsResult<DataFrame> {{")?;
+        writeln!(out, "                DataFrame::new(vec![")?;
+        for field in &model.fields {
+                let column = format!("{:?}.into()", field.column.name);
+                let series = match (&field.rust, field.nullable) {
+                        (RustType::Plain(_), _) => format!("Series::new({column}, self.{})", field.ident),
+                        (RustType::Enum { name, .. }, true) => {
+                                format!("{name}::to_series({column}, self.{})?", field.ident)
+                        }
+                        (RustType::Enum { name, .. }, false) => {
+                                format!("{name}::to_series({column}, self.{}.into_iter().map(Some))?", field.ident)
+                        }
+                };
+                writeln!(out, "                        {series}.into(),")?;
+        }
+        writeln!(out, "                ])")?;
+        writeln!(out, "        }}")?;
+        writeln!(out, "}}")?;
+        writeln!(out, "impl Extend<{name}> for VecOf{name} {{")?;
+        writeln!(out, "        fn extend<I: IntoIterator<Item = {name}>>(&mut self, rows: I) {{")?;
+        writeln!(out, "                rows.into_iter().for_each(|row| self.push(row));")?;
+        writeln!(out, "        }}")?;
+        writeln!(out, "}}")?;
+        writeln!(out, "impl FromIterator<{name}> for VecOf{name} {{")?;
+        writeln!(out, "        fn from_iter<I: IntoIterator<Item = {name}>>(rows: I) -> Self {{")?;
+        writeln!(out, "                let mut columns = Self::default();")?;
+        writeln!(out, "                columns.extend(rows);")?;
+        writeln!(out, "                columns")?;
+        writeln!(out, "        }}")?;
+        writeln!(out, "}}")
+}
+
+/// `students` ~~> `student`, `categories` ~~> `category`; anything else is kept
+fn singular(table: &str) -> String {
+        if let Some(stem) = table.strip_suffix("ies") {
+                format!("{stem}y")
+        } else if table.ends_with('s') && !table.ends_with("ss") {
+                table[..table.len() - 1].to_string()
+        } else {
+                table.to_string()
+        }
+}
+
+/// `DateOfBirth` ~~> `date_of_birth`; keywords become raw identifiers, leading digits get a `_`
+fn field_ident(column: &str) -> String {
+        let ident = column.to_snake_case();
+        match ident.as_str() {
+                "" => "_".to_string(),
+                "self" | "super" | "crate" | "Self" => format!("{ident}_"),
+                keyword if KEYWORDS.contains(&keyword) => format!("r#{ident}"),
+                _ if ident.starts_with(|c: char| c.is_ascii_digit()) => format!("_{ident}"),
+                _ => ident,
+        }
+}
+
+/// `Incomplete` ~~> `Incomplete`, `in progress` ~~> `InProgress`, `1st` ~~> `V1st`
+fn variant_ident(value: &str) -> String {
+        let ident = value.to_upper_camel_case();
+        match ident.chars().next() {
+                None => "Empty".to_string(),
+                Some(c) if c.is_ascii_digit() => format!("V{ident}"),
+                Some(_) if ident == "Self" => "Self_".to_string(),
+                Some(_) => ident,
+        }
+}
+
+const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "do", "dyn", "else", "enum", "extern", "false",
+        "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut",
+        "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof",
+        "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
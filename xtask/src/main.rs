@@ -16,10 +16,15 @@
 //! Note: as long as  (1)`xtask/` is displayed prominently in root
 //!       and (2) a clap interface with command descriptions is present
 //!       then basic command discoverability should be on par with just
+mod gen_models;
 mod types_manual;
 
+use std::{error::Error,
+          path::{Path, PathBuf}};
+
 use clap::Parser;
 use owo_colors::{self as _, OwoColorize};
+use xp_sqlx::{connection::ConnectionArgs, schema::Schema};
 
 use crate::types_manual::*;
 
@@ -34,10 +39,40 @@ enum Args {
         /// List prime components of a rust std type
         // #[arg[(value_enum = "TypesManual")]]
         Primes { t: TypesManual },
+        /// Write a module of row structs (FromRow + ToDataFrame, with a columnar companion) from the live schema
+        GenModels {
+                /// tables to generate (all when omitted)
+                tables:     Vec<String>,
+                /// module to write
+                #[arg(short, long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../crates/xp-sqlx/src/models.rs"))]
+                output:     PathBuf,
+                /// don't write; fail if `--output` isn't what would be generated
+                #[arg(long)]
+                check:      bool,
+                /// read the schema from a `choose_query schema --json` dump instead of the database
+                #[arg(long, value_name = "FILE")]
+                from_json:  Option<PathBuf>,
+                /// parse the schema from `CREATE TABLE` statements (e.g. `data/db_gen/database_students.sql`) instead
+                #[arg(long, value_name = "FILE", conflicts_with = "from_json")]
+                ddl:        Option<PathBuf>,
+                #[command(flatten)]
+                connection: ConnectionArgs,
+        },
 }
 
 fn main() {
         match Args::parse() {
+                Args::GenModels { tables, output, check, from_json, ddl, connection } => {
+                        let source = match (from_json, ddl) {
+                                (Some(path), _) => SchemaSource::Json(path),
+                                (None, Some(path)) => SchemaSource::Ddl(path),
+                                (None, None) => SchemaSource::Database(connection),
+                        };
+                        if let Err(e) = gen_models(&source, &tables, &output, check) {
+                                eprintln!("{}: {e}", "gen-models".red());
+                                std::process::exit(1);
+                        }
+                }
                 Args::Hello => println!("Hello, world"),
                 Args::Add { a, b } => {
                         let sum = a + b;
@@ -100,6 +135,59 @@ fn main() {
         }
 }
 
+/// Where `gen-models` reads the schema from
+enum SchemaSource {
+        Database(ConnectionArgs),
+        Json(PathBuf),
+        Ddl(PathBuf),
+}
+impl SchemaSource {
+        /// `tables` (all if empty) of the schema
+        fn load(&self, tables: &[String]) -> Result<Schema, Box<dyn Error>> {
+                let mut schema = match self {
+                        Self::Database(connection) => tokio::runtime::Builder::new_current_thread()
+                                .enable_all()
+                                .build()?
+                                .block_on(async {
+                                        let pool = connection.connect().await?;
+                                        Schema::load(&pool, tables).await
+                                })?,
+                        Self::Json(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+                        Self::Ddl(path) => Schema::from_ddl(&std::fs::read_to_string(path)?)?,
+                };
+                if let Some(missing) = tables.iter().find(|t| schema.table(t).is_none()) {
+                        return Err(format!("no table named `{missing}` in `{}`", schema.database).into());
+                }
+                if !tables.is_empty() {
+                        schema.tables
+                                .retain(|table| tables.iter().any(|t| t.eq_ignore_ascii_case(&table.name)));
+                }
+                Ok(schema)
+        }
+}
+
+/// Render `source`'s schema, then write (or, with `check`, compare with) `output`
+fn gen_models(source: &SchemaSource, tables: &[String], output: &Path, check: bool) -> Result<(), Box<dyn Error>> {
+        let schema = source.load(tables)?;
+        let generated = gen_models::render(&schema)?;
+        let current = std::fs::read_to_string(output).ok();
+        if check {
+                if current.as_deref() != Some(generated.as_str()) {
+                        return Err(format!(
+                                "{} is out of date; run `cargo run -p xtask -- gen-models`",
+                                output.display()
+                        )
+                        .into());
+                }
+                return Ok(());
+        }
+        if current.as_deref() != Some(generated.as_str()) {
+                std::fs::write(output, &generated)?;
+        }
+        println!("{} {} ({} tables)", "wrote".green(), output.display(), schema.tables.len());
+        Ok(())
+}
+
 /// I'll be surprised if this works efficiently as a mechanical, literal, procedure.
 fn prime_sieve(min: Option<usize>, max: usize) -> Vec<usize> {
         // buncha default yes's